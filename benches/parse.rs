@@ -0,0 +1,91 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use schemars::schema::Schema;
+use schemars_to_zod::Parser;
+use serde_json::{Map, Value, json};
+
+/// An OpenAPI document with `count` schemas, each
+/// referencing the one before it
+fn document(count: usize) -> Value {
+    let mut schemas = Map::new();
+
+    for i in 0..count {
+        let mut properties = json!({
+            "id": { "type": "string", "format": "uuid" },
+            "name": { "type": "string", "minLength": 1 },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "kind": { "type": "string", "enum": ["a", "b", "c"] },
+        });
+
+        if i > 0 {
+            properties["previous"] =
+                json!({ "$ref": format!("#/components/schemas/Type{}", i - 1) });
+        }
+
+        schemas.insert(
+            format!("Type{i}"),
+            json!({ "type": "object", "required": ["id", "name"], "properties": properties }),
+        );
+    }
+
+    json!({ "components": { "schemas": schemas } })
+}
+
+/// A schema with objects nested `depth` times
+fn nested(depth: usize) -> Schema {
+    let mut schema = json!({ "type": "string" });
+
+    for _ in 0..depth {
+        schema = json!({
+            "type": "object",
+            "required": ["inner"],
+            "properties": { "inner": schema, "list": { "type": "array", "items": schema } },
+        });
+    }
+
+    serde_json::from_value(schema).unwrap()
+}
+
+fn module(c: &mut Criterion) {
+    let document = document(1500);
+    let parser = Parser::default();
+    let mut group = c.benchmark_group("module of 1500 schemas");
+
+    group.bench_function("parse_openapi", |b| {
+        b.iter(|| parser.parse_openapi(black_box(&document)).unwrap())
+    });
+    group.bench_function("parse_openapi_into", |b| {
+        let mut buffer = String::new();
+
+        b.iter(|| {
+            buffer.clear();
+            parser
+                .parse_openapi_into(black_box(&document), &mut buffer)
+                .unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn schema(c: &mut Criterion) {
+    let schema = nested(8);
+    let parser = Parser::default();
+    let mut group = c.benchmark_group("schema nested 8 times");
+
+    group.bench_function("parse", |b| {
+        b.iter(|| parser.parse(black_box(&schema)).unwrap())
+    });
+    group.bench_function("parse_into", |b| {
+        let mut buffer = String::new();
+
+        b.iter(|| {
+            buffer.clear();
+            parser.parse_into(black_box(&schema), &mut buffer).unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, module, schema);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+#!/bin/sh
+# Bundle zod/mini into vendor/zod-mini.js, a script
+# defining the global `z`, which the tests of the
+# `validate` feature evaluate instead of node
+set -e
+
+cd "$(dirname "$0")/.."
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+npm install --silent --prefix "$tmp" zod@^4 esbuild
+echo 'export * from "zod/mini";' > "$tmp/entry.js"
+
+mkdir -p vendor
+"$tmp/node_modules/.bin/esbuild" "$tmp/entry.js" --bundle --minify \
+    --format=iife --global-name=z --target=es2020 \
+    --outfile=vendor/zod-mini.js
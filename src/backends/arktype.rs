@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+use super::{
+    AdditionalProperties,
+    Backend,
+    Js,
+    Property,
+    codec_functions,
+    content_text,
+    dependent_required_check,
+    format_regex,
+    js,
+    js_doc,
+};
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+/// Outputs ArkType types
+///
+/// ArkType has no codecs, so codecs and embedded
+/// json are only decoded. Defaults are only
+/// applied to properties.
+pub struct ArkTypeBackend;
+
+impl ArkTypeBackend {
+    /// The ArkType type validating a string format
+    fn format_type(format: &str) -> Option<String> {
+        Some(String::from(match format {
+            "email" => r#"type("string.email")"#,
+            "uri" | "iri" => r#"type("string.url")"#,
+            "uuid" => r#"type("string.uuid")"#,
+            "ipv4" => r#"type("string.ip.v4")"#,
+            "ipv6" => r#"type("string.ip.v6")"#,
+            "byte" | "base64" => r#"type("string.base64")"#,
+            "base64url" => r#"type("string.base64.url")"#,
+            "hex" | "base16" => r#"type("string.hex")"#,
+            "regex" => r#"type("string.regex")"#,
+            // there is no validator in ArkType
+            "idn-email"
+            | "uri-reference"
+            | "iri-reference"
+            | "uri-template"
+            | "idn-hostname"
+            | "json-pointer"
+            | "relative-json-pointer" => r#"type("string")"#,
+            _ => return format_regex(format).map(|regex| format!("type({regex})")),
+        }))
+    }
+}
+
+impl Backend for ArkTypeBackend {
+    fn unknown(&self) -> JsResult { Ok(Js::from(r#"type("unknown")"#)) }
+
+    fn any(&self) -> JsResult { Ok(Js::from(r#"type("unknown.any")"#)) }
+
+    fn never(&self) -> JsResult { Ok(Js::from(r#"type("never")"#)) }
+
+    fn null(&self) -> JsResult { Ok(Js::from(r#"type("null")"#)) }
+
+    fn boolean(&self) -> JsResult { Ok(Js::from(r#"type("boolean")"#)) }
+
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult {
+        let mut res = if is_int {
+            String::from(r#"type("number.integer")"#)
+        } else {
+            String::from(r#"type("number")"#)
+        };
+
+        if let Some(multiple_of) = options.multiple_of {
+            res.push_str(&format!(".divisibleBy({multiple_of})"));
+        }
+
+        if let Some(val) = options.minimum {
+            res.push_str(&format!(".atLeast({val})"));
+        }
+
+        if let Some(val) = options.exclusive_minimum {
+            res.push_str(&format!(".moreThan({val})"));
+        }
+
+        if let Some(val) = options.maximum {
+            res.push_str(&format!(".atMost({val})"));
+        }
+
+        if let Some(val) = options.exclusive_maximum {
+            res.push_str(&format!(".lessThan({val})"));
+        }
+
+        Ok(res.into())
+    }
+
+    fn is_format_supported(&self, format: &str) -> bool {
+        Self::format_type(format).is_some()
+    }
+
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult {
+        let mut res = format
+            .and_then(Self::format_type)
+            .unwrap_or_else(|| String::from(r#"type("string")"#));
+
+        if let Some(min_length) = options.min_length
+            && let Some(max_length) = options.max_length
+            && min_length == max_length
+        {
+            res.push_str(&format!(".exactlyLength({min_length})"));
+        } else {
+            if let Some(min_length) = options.min_length {
+                res.push_str(&format!(".atLeastLength({min_length})"));
+            }
+
+            if let Some(max_length) = options.max_length {
+                res.push_str(&format!(".atMostLength({max_length})"));
+            }
+        }
+
+        if let Some(pattern) = &options.pattern {
+            res.push_str(&format!(
+                ".matching(new RegExp({}))",
+                serde_json::to_string(&pattern)?
+            ));
+        }
+
+        Ok(res.into())
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            DateFormat::CoerceDate => r#"type("string.date.parse")"#,
+            DateFormat::JsDate => r#"type("Date")"#,
+            DateFormat::DateToJson =>
+                r#"type("Date").narrow(date => date.toJSON() !== null)"#,
+            DateFormat::IsoStringDate | DateFormat::Codec =>
+                return Err(Error::ForgotCheck(
+                    "Date: the date format has to turn the string into a Date",
+                )),
+        }))
+    }
+
+    fn codec(&self, format: &str) -> JsResult {
+        let (Some(input), Some((decode, _))) =
+            (Self::format_type(format), codec_functions(format))
+        else {
+            return Err(Error::ForgotCheck(
+                "Codec: parse_codec requires a format supported by is_codec",
+            ));
+        };
+
+        Ok(format!("{input}.pipe({decode})").into())
+    }
+
+    fn content(&self, encoding: Option<&str>, inner: Js, _codec: bool) -> JsResult {
+        let text = content_text(encoding)?;
+        let input = self.string(encoding, &Default::default())?;
+
+        Ok(js!(
+            input,
+            format!(
+                ".pipe((s, ctx) => {{ try {{ return JSON.parse({text}); }} catch {{ \
+                 return ctx.error('valid JSON'); }} }}).to("
+            ),
+            inner,
+            ")",
+        ))
+    }
+
+    fn literal(&self, value: &Value) -> JsResult {
+        Ok(format!("type.unit({})", serde_json::to_string(value)?).into())
+    }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult {
+        let mut converted = Vec::with_capacity(values.len());
+
+        for value in values {
+            converted.push(serde_json::to_string(value)?);
+        }
+
+        Ok(format!("type.enumerated({})", converted.join(", ")).into())
+    }
+
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        let mut object_inner = Vec::with_capacity(properties.len());
+
+        for property in properties {
+            let entry = if let Some(default) = &property.default {
+                let json = serde_json::to_string(default)?;
+                // a default which isn't a primitive has to
+                // be created by a thunk, so it isn't shared
+                let default = match default {
+                    Value::Object(_) => format!("() => ({json})"),
+                    Value::Array(_) => format!("() => {json}"),
+                    _ => json,
+                };
+
+                js!(
+                    format!("{}: [", serde_json::to_string(&property.key)?),
+                    property.schema,
+                    format!(", \"=\", {default}]"),
+                )
+            } else if property.optional {
+                js!(
+                    format!(
+                        "{}: ",
+                        serde_json::to_string(&format!("{}?", property.key))?
+                    ),
+                    property.schema,
+                )
+            } else {
+                js!(
+                    format!("{}: ", serde_json::to_string(&property.key)?),
+                    property.schema,
+                )
+            };
+
+            object_inner.push(entry);
+        }
+
+        match additional {
+            AdditionalProperties::Unset =>
+                if object_inner.is_empty() {
+                    return Ok(Js::from(r#"type("Record<string, unknown>")"#));
+                },
+            AdditionalProperties::Forbidden =>
+                if object_inner.is_empty() {
+                    return Err(Error::Unimplemented(
+                        "Object: additional_properties are false, and there are no \
+                         properties given",
+                    ));
+                },
+            AdditionalProperties::Any(additional)
+            | AdditionalProperties::Schema(additional) =>
+                object_inner.push(js!(r#""[string]": "#, additional)),
+        }
+
+        Ok(js!("type({ ", Js::join(object_inner, ", "), " })"))
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        Ok(js!(
+            schema,
+            format!(".narrow({})", dependent_required_check(dependencies)?),
+        ))
+    }
+
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult {
+        let mut res = vec![items, Js::from(".array()")];
+
+        if let Some(min_items) = options.min_items {
+            res.push(format!(".atLeastLength({min_items})").into());
+        }
+
+        if let Some(max_items) = options.max_items {
+            res.push(format!(".atMostLength({max_items})").into());
+        }
+
+        Ok(res.into())
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        Ok(if let Some(rest) = rest {
+            js!(
+                "type([",
+                Js::join(items, ", "),
+                r#", "...", "#,
+                rest,
+                ".array()])"
+            )
+        } else {
+            js!("type([", Js::join(items, ", "), "])")
+        })
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult {
+        let mut variants = variants.into_iter();
+        let mut res = vec![variants.next().ok_or(Error::ForgotCheck(
+            "Union: a union requires at least one variant",
+        ))?];
+
+        for variant in variants {
+            res.extend([Js::from(".or("), variant, Js::from(")")]);
+        }
+
+        Ok(res.into())
+    }
+
+    fn discriminated_union(&self, _key: &str, variants: Vec<Js>) -> JsResult {
+        // ArkType finds the discriminant on its own
+        self.union(variants)
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        Ok(js!(left, ".and(", right, ")"))
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { Ok(js!(schema, r#".or("null")"#)) }
+
+    fn default(&self, schema: Js, _default: &Value, _prefault: bool) -> JsResult {
+        // ArkType only has defaults for properties,
+        // which are added in `object`
+        Ok(schema)
+    }
+
+    fn reference(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn lazy(&self, _name: &str) -> JsResult {
+        Err(Error::Unimplemented(
+            "Reference: cyclic references are only supported in an ArkType scope",
+        ))
+    }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        Ok(js!(
+            variant,
+            format!(".merge({{ {}: ", serde_json::to_string(key)?),
+            self.literal(value)?,
+            " })",
+        ))
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult {
+        Ok(format!("typeof {name}.inferIn"))
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        Ok(format!("{name}.assert({value})"))
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult {
+        Ok(format!("typeof {name}.infer"))
+    }
+
+    // the morphs of ArkType only decode
+    fn encode_value(&self, _name: &str, _value: &str) -> Option<String> { None }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> {
+        Some(("{ type }", "arktype"))
+    }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}const {name} = ",
+                js_doc(description),
+                if export { "export " } else { "" }
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    // the type of a morph can't be declared
+    fn schema_type(&self, output: &str, _input: &str) -> Option<String> {
+        Some(format!("type.Any<{output}>"))
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        Ok(js!(format!("{name}("), Js::join(arguments, ", "), ")"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema::Schema;
+    use serde_json::json;
+
+    use crate::{Config, Parser, Target};
+
+    #[test]
+    fn test_arktype_defaults() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "default": 1 },
+                "tags": { "type": "array", "items": { "type": "string" }, "default": [] },
+                "meta": {
+                    "type": "object",
+                    "properties": { "id": { "type": "string" } },
+                    "default": { "id": "a" },
+                },
+            },
+        }))
+        .unwrap();
+
+        let parser = Parser::new(Config {
+            target: Target::ArkType,
+            ..Default::default()
+        });
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"type({ "count": [type("number.integer"), "=", 1], "meta": "#,
+                r#"[type({ "id?": type("string") }), "=", () => ({"id":"a"})], "#,
+                r#""tags": [type("string").array(), "=", () => []] })"#,
+            ),
+            &result
+        );
+        crate::parsers::check_with(crate::ARKTYPE_IMPORT, result);
+    }
+}
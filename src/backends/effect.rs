@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+use super::{
+    AdditionalProperties,
+    Backend,
+    Js,
+    Property,
+    codec_functions,
+    dependent_required_check,
+    format_regex,
+    js,
+    js_doc,
+};
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+/// Outputs effect/Schema schemas
+///
+/// Defaults are only applied to properties.
+pub struct EffectBackend;
+
+impl EffectBackend {
+    /// The effect schema validating a string format
+    fn format_schema(format: &str) -> Option<String> {
+        Some(String::from(match format {
+            "uuid" => "Schema.UUID",
+            "ulid" => "Schema.ULID",
+            "uri" | "iri" => "Schema.String.pipe(Schema.filter(s => URL.canParse(s)))",
+            "regex" =>
+                "Schema.String.pipe(Schema.filter(s => { try { new RegExp(s); return \
+                 true; } catch { return false; } }))",
+            // there is no validator in effect
+            "idn-email"
+            | "uri-reference"
+            | "iri-reference"
+            | "uri-template"
+            | "idn-hostname"
+            | "json-pointer"
+            | "relative-json-pointer" => "Schema.String",
+            _ =>
+                return format_regex(format)
+                    .map(|regex| format!("Schema.String.pipe(Schema.pattern({regex}))")),
+        }))
+    }
+
+    /// Pipe a schema through its filters
+    fn pipe(schema: Js, filters: Vec<String>) -> Js {
+        if filters.is_empty() {
+            schema
+        } else {
+            js!(schema, format!(".pipe({})", filters.join(", ")))
+        }
+    }
+}
+
+impl Backend for EffectBackend {
+    fn unknown(&self) -> JsResult { Ok(Js::from("Schema.Unknown")) }
+
+    fn any(&self) -> JsResult { Ok(Js::from("Schema.Any")) }
+
+    fn never(&self) -> JsResult { Ok(Js::from("Schema.Never")) }
+
+    fn null(&self) -> JsResult { Ok(Js::from("Schema.Null")) }
+
+    fn boolean(&self) -> JsResult { Ok(Js::from("Schema.Boolean")) }
+
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult {
+        let mut filters = Vec::new();
+
+        if let Some(multiple_of) = options.multiple_of {
+            filters.push(format!("Schema.multipleOf({multiple_of})"));
+        }
+
+        if let Some(val) = options.minimum {
+            filters.push(format!("Schema.greaterThanOrEqualTo({val})"));
+        }
+
+        if let Some(val) = options.exclusive_minimum {
+            filters.push(format!("Schema.greaterThan({val})"));
+        }
+
+        if let Some(val) = options.maximum {
+            filters.push(format!("Schema.lessThanOrEqualTo({val})"));
+        }
+
+        if let Some(val) = options.exclusive_maximum {
+            filters.push(format!("Schema.lessThan({val})"));
+        }
+
+        let schema = if is_int {
+            "Schema.Int"
+        } else {
+            "Schema.Number"
+        };
+
+        Ok(Self::pipe(Js::from(schema), filters))
+    }
+
+    fn is_format_supported(&self, format: &str) -> bool {
+        Self::format_schema(format).is_some()
+    }
+
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult {
+        let mut filters = Vec::new();
+
+        if let Some(min_length) = options.min_length
+            && let Some(max_length) = options.max_length
+            && min_length == max_length
+        {
+            filters.push(format!("Schema.length({min_length})"));
+        } else {
+            if let Some(min_length) = options.min_length {
+                filters.push(format!("Schema.minLength({min_length})"));
+            }
+
+            if let Some(max_length) = options.max_length {
+                filters.push(format!("Schema.maxLength({max_length})"));
+            }
+        }
+
+        if let Some(pattern) = &options.pattern {
+            filters.push(format!(
+                "Schema.pattern(new RegExp({}))",
+                serde_json::to_string(&pattern)?
+            ));
+        }
+
+        let schema = format
+            .and_then(Self::format_schema)
+            .unwrap_or_else(|| String::from("Schema.String"));
+
+        Ok(Self::pipe(schema.into(), filters))
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            DateFormat::CoerceDate => "Schema.Date",
+            DateFormat::JsDate => "Schema.DateFromSelf",
+            DateFormat::DateToJson => "Schema.ValidDateFromSelf",
+            DateFormat::IsoStringDate | DateFormat::Codec =>
+                return Err(Error::ForgotCheck(
+                    "Date: the date format has to turn the string into a Date",
+                )),
+        }))
+    }
+
+    fn codec(&self, format: &str) -> JsResult {
+        let output = match format {
+            "date-time" | "partial-date-time" | "date" => "Schema.DateFromSelf",
+            "duration" =>
+                "Schema.NonNegativeInt.pipe(Schema.lessThanOrEqualTo(Number.\
+                 MAX_SAFE_INTEGER))",
+            "byte" | "base64" => "Schema.Uint8ArrayFromSelf",
+            "int64" | "uint64" => "Schema.BigIntFromSelf",
+            _ =>
+                return Err(Error::ForgotCheck(
+                    "Codec: parse_codec requires a format supported by is_codec",
+                )),
+        };
+
+        let (Some(input), Some((decode, encode))) =
+            (Self::format_schema(format), codec_functions(format))
+        else {
+            return Err(Error::ForgotCheck(
+                "Codec: the format has to have a schema and codec functions",
+            ));
+        };
+
+        Ok(format!(
+            "Schema.transform({input}, {output}, {{ strict: true, decode: {decode}, \
+             encode: {encode} }})"
+        )
+        .into())
+    }
+
+    fn content(&self, encoding: Option<&str>, inner: Js, _codec: bool) -> JsResult {
+        // effect always encodes as well
+        let json = js!("Schema.parseJson(", inner, ")");
+
+        Ok(match encoding {
+            None => json,
+            Some("base64") => js!("Schema.compose(Schema.StringFromBase64, ", json, ")"),
+            Some("base64url") =>
+                js!("Schema.compose(Schema.StringFromBase64Url, ", json, ")"),
+            Some("hex" | "base16") =>
+                js!("Schema.compose(Schema.StringFromHex, ", json, ")"),
+            Some(_) =>
+                return Err(Error::Unimplemented(
+                    "Content: only the content encodings base64, base64url and hex are \
+                     supported",
+                )),
+        })
+    }
+
+    fn literal(&self, value: &Value) -> JsResult {
+        Ok(match value {
+            Value::Array(_) | Value::Object(_) =>
+                return Err(Error::Unimplemented(
+                    "Literal: only primitive literals are supported",
+                )),
+            _ => format!("Schema.Literal({})", serde_json::to_string(value)?).into(),
+        })
+    }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult {
+        let mut converted = Vec::with_capacity(values.len());
+
+        for value in values {
+            if matches!(value, Value::Array(_) | Value::Object(_)) {
+                return Err(Error::Unimplemented(
+                    "Enum: only primitive values are supported",
+                ));
+            }
+
+            converted.push(serde_json::to_string(value)?);
+        }
+
+        Ok(format!("Schema.Literal({})", converted.join(", ")).into())
+    }
+
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        let mut object_inner = Vec::with_capacity(properties.len());
+
+        for property in properties {
+            let schema = if let Some(default) = &property.default {
+                // the default is given in its json form
+                js!(
+                    "Schema.optionalWith(",
+                    property.schema.clone(),
+                    ", { default: () => Schema.decodeUnknownSync(",
+                    property.schema,
+                    format!(")({}) }})", serde_json::to_string(default)?),
+                )
+            } else if property.optional {
+                js!("Schema.optional(", property.schema, ")")
+            } else {
+                property.schema
+            };
+
+            let key = serde_json::to_string(&property.key)?;
+
+            object_inner.push(js!(format!("{key}: "), schema));
+        }
+
+        let record =
+            |value: Js| js!("Schema.Record({ key: Schema.String, value: ", value, " })");
+
+        Ok(match additional {
+            AdditionalProperties::Unset if object_inner.is_empty() =>
+                record(Js::from("Schema.Unknown")),
+            AdditionalProperties::Forbidden if object_inner.is_empty() =>
+                return Err(Error::Unimplemented(
+                    "Object: additional_properties are false, and there are no \
+                     properties given",
+                )),
+            AdditionalProperties::Unset | AdditionalProperties::Forbidden =>
+                js!("Schema.Struct({ ", Js::join(object_inner, ", "), " })"),
+            AdditionalProperties::Any(additional)
+            | AdditionalProperties::Schema(additional) =>
+                if object_inner.is_empty() {
+                    record(additional)
+                } else {
+                    js!(
+                        "Schema.Struct({ ",
+                        Js::join(object_inner, ", "),
+                        " }, ",
+                        record(additional),
+                        ")",
+                    )
+                },
+        })
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        Ok(js!(
+            schema,
+            format!(
+                ".pipe(Schema.filter({}))",
+                dependent_required_check(dependencies)?
+            ),
+        ))
+    }
+
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult {
+        let mut filters = Vec::new();
+
+        if let Some(min_items) = options.min_items {
+            filters.push(format!("Schema.minItems({min_items})"));
+        }
+
+        if let Some(max_items) = options.max_items {
+            filters.push(format!("Schema.maxItems({max_items})"));
+        }
+
+        Ok(Self::pipe(js!("Schema.Array(", items, ")"), filters))
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        Ok(if let Some(rest) = rest {
+            js!("Schema.Tuple([", Js::join(items, ", "), "], ", rest, ")")
+        } else {
+            js!("Schema.Tuple(", Js::join(items, ", "), ")")
+        })
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult {
+        Ok(js!("Schema.Union(", Js::join(variants, ", "), ")"))
+    }
+
+    fn discriminated_union(&self, _key: &str, variants: Vec<Js>) -> JsResult {
+        // effect finds the discriminant on its own
+        self.union(variants)
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        Ok(js!("Schema.extend(", left, ", ", right, ")"))
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { Ok(js!("Schema.NullOr(", schema, ")")) }
+
+    fn default(&self, schema: Js, _default: &Value, _prefault: bool) -> JsResult {
+        // effect only has defaults for properties,
+        // which are added in `object`
+        Ok(schema)
+    }
+
+    fn reference(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn lazy(&self, name: &str) -> JsResult {
+        Ok(format!("Schema.suspend(() => {name})").into())
+    }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        Ok(js!(
+            "Schema.Struct({ ...",
+            variant,
+            format!(".fields, {}: ", serde_json::to_string(key)?),
+            self.literal(value)?,
+            " })",
+        ))
+    }
+
+    fn lazy_with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        Ok(js!(
+            "Schema.suspend(() => ",
+            self.with_discriminant(variant, key, value)?,
+            ")"
+        ))
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult {
+        Ok(format!("typeof {name}.Encoded"))
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        Ok(format!("Schema.decodeUnknownSync({name})({value})"))
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult {
+        Ok(format!("typeof {name}.Type"))
+    }
+
+    fn encode_value(&self, name: &str, value: &str) -> Option<String> {
+        Some(format!("Schema.encodeSync({name})({value})"))
+    }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> {
+        Some(("{ Schema }", "effect"))
+    }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}const {name} = ",
+                js_doc(description),
+                if export { "export " } else { "" }
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    fn schema_type(&self, output: &str, input: &str) -> Option<String> {
+        Some(match output == input {
+            true => format!("Schema.Schema<{output}>"),
+            false => format!("Schema.Schema<{output}, {input}>"),
+        })
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        Ok(js!(format!("{name}("), Js::join(arguments, ", "), ")"))
+    }
+}
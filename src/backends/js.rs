@@ -0,0 +1,156 @@
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter, Write},
+};
+
+/// Concatenate parts into a `Js`, which are
+/// anything `Js` implements `From` for
+macro_rules! js {
+    ($($part:expr),+ $(,)?) => {
+        $crate::backends::Js::Concat(vec![$($crate::backends::Js::from($part)),+])
+    };
+}
+
+pub(crate) use js;
+
+/// Parsed js or TypeScript, built from the parsed
+/// parts of a schema without copying them
+///
+/// It is only turned into text once, when it is
+/// written.
+#[derive(Clone, Debug)]
+pub enum Js {
+    /// A piece of text
+    Text(Cow<'static, str>),
+    /// Parts following each other
+    Concat(Vec<Js>),
+}
+
+impl Js {
+    /// Concatenate `parts`
+    pub fn concat(parts: impl IntoIterator<Item = Js>) -> Self {
+        Js::Concat(parts.into_iter().collect())
+    }
+
+    /// Concatenate `parts`, with `separator` between
+    /// each of them
+    pub fn join(parts: Vec<Js>, separator: &'static str) -> Self {
+        let mut joined = Vec::with_capacity(parts.len() * 2);
+
+        for part in parts {
+            if !joined.is_empty() {
+                joined.push(Js::from(separator));
+            }
+            joined.push(part);
+        }
+
+        Js::Concat(joined)
+    }
+
+    /// Write the text into `writer`
+    pub fn write_to(&self, writer: &mut impl Write) -> std::fmt::Result {
+        match self {
+            Js::Text(text) => writer.write_str(text),
+            Js::Concat(parts) => parts.iter().try_for_each(|part| part.write_to(writer)),
+        }
+    }
+
+    /// Check if the text contains `c`
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Js::Text(text) => text.contains(c),
+            Js::Concat(parts) => parts.iter().any(|part| part.contains(c)),
+        }
+    }
+
+    /// Check if the text ends with `c`
+    pub fn ends_with(&self, c: char) -> bool {
+        match self {
+            Js::Text(text) => text.ends_with(c),
+            Js::Concat(parts) => parts
+                .iter()
+                .rev()
+                .find(|part| !part.is_empty())
+                .is_some_and(|part| part.ends_with(c)),
+        }
+    }
+
+    /// Check if there is no text
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Js::Text(text) => text.is_empty(),
+            Js::Concat(parts) => parts.iter().all(Js::is_empty),
+        }
+    }
+
+    /// The part wrapped by `prefix` and `suffix`,
+    /// e.g. the schema of `z.nullable(<schema>)`, or
+    /// itself if it isn't wrapped by them
+    pub fn strip_wrapper(self, prefix: &str, suffix: &str) -> Result<Js, Js> {
+        match self {
+            Js::Text(text) => match text
+                .strip_prefix(prefix)
+                .and_then(|text| text.strip_suffix(suffix))
+            {
+                Some(inner) => Ok(Js::from(inner.to_owned())),
+                None => Err(Js::Text(text)),
+            },
+            Js::Concat(mut parts) => {
+                let is_wrapped = parts.len() >= 2
+                    && matches!(parts.first(), Some(Js::Text(first)) if first == prefix)
+                    && matches!(parts.last(), Some(Js::Text(last)) if last == suffix);
+
+                if !is_wrapped {
+                    return Err(Js::Concat(parts));
+                }
+
+                parts.pop();
+                parts.remove(0);
+                Ok(Js::Concat(parts))
+            },
+        }
+    }
+}
+
+impl Display for Js {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result { self.write_to(f) }
+}
+
+impl From<&'static str> for Js {
+    fn from(value: &'static str) -> Self { Js::Text(Cow::Borrowed(value)) }
+}
+
+impl From<String> for Js {
+    fn from(value: String) -> Self { Js::Text(Cow::Owned(value)) }
+}
+
+impl From<Vec<Js>> for Js {
+    fn from(value: Vec<Js>) -> Self { Js::Concat(value) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Js;
+
+    #[test]
+    fn test_js() {
+        let items = Js::join(vec![Js::from("a"), Js::from(String::from("b"))], ", ");
+        let nullable = js!("z.nullable(", js!("z.array([", items, "])"), ")");
+
+        assert_eq!("z.nullable(z.array([a, b]))", nullable.to_string());
+        assert!(nullable.contains('b'));
+        assert!(nullable.ends_with(')'));
+        assert_eq!(
+            "z.array([a, b])",
+            nullable
+                .strip_wrapper("z.nullable(", ")")
+                .unwrap()
+                .to_string()
+        );
+        assert!(
+            Js::from("z.string()")
+                .strip_wrapper("z.nullable(", ")")
+                .is_err()
+        );
+    }
+}
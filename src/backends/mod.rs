@@ -0,0 +1,437 @@
+//! The outputs of the parser, e.g. zod or
+//! TypeScript types
+
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+#[cfg(feature = "pretty")]
+use crate::PrettyConfig;
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+mod arktype;
+mod effect;
+mod js;
+mod typescript;
+mod valibot;
+mod zod;
+
+pub use arktype::ArkTypeBackend;
+pub use effect::EffectBackend;
+pub use js::Js;
+pub(crate) use js::js;
+pub use typescript::TypeScriptBackend;
+pub(crate) use typescript::TypeScriptOutputBackend;
+pub use valibot::ValibotBackend;
+pub use zod::ZodBackend;
+
+/// A property of an object, which is already
+/// parsed
+pub struct Property {
+    /// The key of the property
+    pub key: String,
+    /// The parsed schema of the property
+    pub schema: Js,
+    /// The property may be left out
+    pub optional: bool,
+    /// The valid default value of the property,
+    /// which `Backend::default` was already
+    /// called with
+    pub default: Option<Value>,
+    /// The description of the property
+    pub description: Option<String>,
+}
+
+/// A parameter of a generic declaration, e.g. the
+/// type `T` and the value `t` of `<T>(t: T)`
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    /// The identifier of its type
+    pub type_name: String,
+    /// The identifier of its value, which a schema
+    /// references
+    pub value: String,
+}
+
+/// The additional properties of an object, which
+/// are already parsed
+pub enum AdditionalProperties {
+    /// `additionalProperties` is not given
+    Unset,
+    /// `additionalProperties: false`
+    Forbidden,
+    /// `additionalProperties: true`
+    Any(Js),
+    /// `additionalProperties: <schema>`
+    Schema(Js),
+}
+
+/// The output of the parser, which the schema
+/// traversal in `ParserInner` calls with the
+/// already parsed parts of a schema
+///
+/// Implement it to output another library, and
+/// use it with `Parser::with_backend`.
+pub trait Backend: Send + Sync {
+    /// `true`, with `Config::prefer_unknown`
+    fn unknown(&self) -> JsResult;
+
+    /// `true`
+    fn any(&self) -> JsResult;
+
+    /// `false`
+    fn never(&self) -> JsResult;
+
+    /// `type: "null"`
+    fn null(&self) -> JsResult;
+
+    /// `type: "boolean"`
+    fn boolean(&self) -> JsResult;
+
+    /// `type: "number"` or `type: "integer"`
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult;
+
+    /// Check if a string format can be validated,
+    /// unknown formats are reported
+    fn is_format_supported(&self, format: &str) -> bool;
+
+    /// `type: "string"`, which is not a date, a
+    /// codec or embedded json
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult;
+
+    /// A date string, with a `DateFormat` which
+    /// doesn't keep it as a string
+    fn date(&self, date_format: DateFormat, format: &str) -> JsResult;
+
+    /// A string format, which is decoded and
+    /// encoded with a codec
+    fn codec(&self, format: &str) -> JsResult;
+
+    /// A string containing embedded json, which
+    /// is parsed with `inner`
+    fn content(&self, encoding: Option<&str>, inner: Js, codec: bool) -> JsResult;
+
+    /// `const: <value>`
+    fn literal(&self, value: &Value) -> JsResult;
+
+    /// `enum: [<values>]`, with two or more values
+    fn r#enum(&self, values: &[Value]) -> JsResult;
+
+    /// `type: "object"`
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult;
+
+    /// `dependentRequired: { <key>: [<keys>] }`,
+    /// where an object with the property `key` also
+    /// has to have the properties `keys`
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult;
+
+    /// `type: "array"`, with one schema for all
+    /// items
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult;
+
+    /// `type: "array"`, with one schema per item
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult;
+
+    /// `anyOf` or `oneOf`
+    fn union(&self, variants: Vec<Js>) -> JsResult;
+
+    /// `anyOf` or `oneOf`, where every variant is an
+    /// object with the literal property `key`
+    fn discriminated_union(&self, key: &str, variants: Vec<Js>) -> JsResult;
+
+    /// A union with its own properties
+    fn intersection(&self, left: Js, right: Js) -> JsResult;
+
+    /// `type: [<type>, "null"]`
+    fn nullable(&self, schema: Js) -> JsResult;
+
+    /// `default: <value>`, where `prefault` means
+    /// that the default is parsed like any other
+    /// input
+    fn default(&self, schema: Js, default: &Value, prefault: bool) -> JsResult;
+
+    /// `$ref: <reference>`, to the declaration
+    /// `name` which is already outputted
+    fn reference(&self, name: &str) -> JsResult;
+
+    /// `$ref: <reference>`, to the declaration
+    /// `name` which is outputted later
+    fn lazy(&self, name: &str) -> JsResult;
+
+    /// An object variant of a discriminated union,
+    /// whose `key` is set to the literal `value`
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult;
+
+    /// An object variant of a discriminated union,
+    /// like `with_discriminant`, whose declaration
+    /// is outputted after the union, e.g. in a cycle
+    ///
+    /// `variant` references the declaration, so the
+    /// variant has to be extended lazily.
+    fn lazy_with_discriminant(
+        &self,
+        _variant: Js,
+        _key: &str,
+        _value: &Value,
+    ) -> JsResult {
+        Err(Error::Unimplemented(
+            "Union: a variant with a discriminant has to be declared before its union",
+        ))
+    }
+
+    /// The type of the input of the declaration
+    /// `name`, e.g. for the arguments of a client
+    fn input_type(&self, name: &str) -> ParserResult;
+
+    /// Parse the js expression `value` with the
+    /// declaration `name`, e.g. for the responses
+    /// of a client
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult;
+
+    /// The type of the output of the declaration
+    /// `name`, which `encode_value` takes
+    fn output_type(&self, name: &str) -> ParserResult;
+
+    /// Encode the js expression `value` into the
+    /// input of the declaration `name`, e.g. for the
+    /// request bodies of a client, if the codecs of
+    /// the backend can be encoded
+    fn encode_value(&self, name: &str, value: &str) -> Option<String>;
+
+    /// The binding and the specifier of the import
+    /// required by the output of a module, e.g.
+    /// `* as z` and `zod/mini`
+    fn import(&self) -> Option<(&'static str, &'static str)>;
+
+    /// A named declaration of a parsed schema, which
+    /// is exported in place with `export`
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult;
+
+    /// The type of a schema, whose output has the
+    /// type `output` and whose input has the type
+    /// `input`, e.g. `z.ZodMiniType<User>`
+    ///
+    /// Outputs without a runtime have no schemas.
+    fn schema_type(&self, output: &str, input: &str) -> Option<String>;
+
+    /// A named generic declaration, a factory of a
+    /// schema which references its `parameters`, by
+    /// their values or, without schemas, by their
+    /// types, which are only `typed` in TypeScript
+    fn generic(
+        &self,
+        name: &str,
+        parameters: &[Parameter],
+        schema: Js,
+        description: Option<&str>,
+        typed: bool,
+        export: bool,
+    ) -> JsResult {
+        let export = if export { "export " } else { "" };
+
+        if !typed {
+            return Ok(js!(
+                format!(
+                    "{}{export}const {name} = ({}) => ",
+                    js_doc(description),
+                    parameters
+                        .iter()
+                        .map(|parameter| parameter.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                schema,
+                ";",
+            ));
+        }
+
+        let Some(bound) = self.schema_type("any", "any") else {
+            return Err(Error::SchemaError(
+                "Generic: the target has no type of its schemas",
+            ));
+        };
+        let types: Vec<_> = parameters
+            .iter()
+            .map(|parameter| format!("{} extends {bound}", parameter.type_name))
+            .collect();
+        let arguments: Vec<_> = parameters
+            .iter()
+            .map(|parameter| format!("{}: {}", parameter.value, parameter.type_name))
+            .collect();
+
+        Ok(js!(
+            format!(
+                "{}{export}const {name} = <{}>({}) => ",
+                js_doc(description),
+                types.join(", "),
+                arguments.join(", ")
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    /// The declaration of the schema `name` in a
+    /// `.d.ts` file, next to the type `name` of its
+    /// json and the type `output` of its output
+    fn declared(
+        &self,
+        name: &str,
+        output: &str,
+        parameters: &[Parameter],
+    ) -> Option<String> {
+        let types: Vec<_> = parameters
+            .iter()
+            .map(|parameter| parameter.type_name.as_str())
+            .collect();
+        let arguments = match parameters.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", types.join(", ")),
+        };
+        let schema_type = self.schema_type(
+            &format!("{output}{arguments}"),
+            &format!("{name}{arguments}"),
+        )?;
+
+        if parameters.is_empty() {
+            return Some(format!("export declare const {name}: {schema_type};"));
+        }
+
+        let mut typed = Vec::with_capacity(parameters.len());
+        for parameter in parameters {
+            typed.push(format!(
+                "{}: {}",
+                parameter.value,
+                self.schema_type(&parameter.type_name, &parameter.type_name)?
+            ));
+        }
+
+        Some(format!(
+            "export declare const {name}: {arguments}({}) => {schema_type};",
+            typed.join(", ")
+        ))
+    }
+
+    /// An instantiation of the generic declaration
+    /// `name` with parsed schemas as `arguments`
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult;
+
+    /// Format a parsed schema
+    #[cfg(feature = "pretty")]
+    fn format(&self, parsed: &str, config: &PrettyConfig) -> ParserResult {
+        crate::pretty::format_js(parsed, ".js", config)
+            .ok()
+            .ok_or(crate::Error::PrettifyError)
+    }
+}
+
+/// The js predicate of `dependentRequired`, which
+/// checks the dependencies of the given properties
+pub(crate) fn dependent_required_check(
+    dependencies: &BTreeMap<String, Vec<String>>,
+) -> Result<String, Error> {
+    Ok(format!(
+        "o => Object.entries({}).every(([k, r]) => !(k in o) || r.every(d => d in o))",
+        serde_json::to_string(dependencies)?
+    ))
+}
+
+/// Turn a description into a JSDoc comment
+pub fn js_doc(description: Option<&str>) -> String {
+    match description {
+        Some(description) => format!("/** {} */\n", description.replace("*/", "*\\/")),
+        None => String::new(),
+    }
+}
+
+/// A js regex validating a string format, for
+/// outputs without a validator of their own
+pub(crate) fn format_regex(format: &str) -> Option<&'static str> {
+    Some(match format {
+        "email" => r"/^[^\s@]+@[^\s@]+\.[^\s@]+$/",
+        "uuid" => r"/^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i",
+        "ipv4" =>
+            r"/^(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)$/",
+        "mac" => r"/^[0-9a-f]{2}(?::[0-9a-f]{2}){5}$/i",
+        "date-time" =>
+            r"/^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})$/i",
+        // naive date times don't have an offset
+        "partial-date-time" => r"/^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?$/",
+        "date" => r"/^\d{4}-\d{2}-\d{2}$/",
+        "time" => r"/^\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})?$/i",
+        "duration" => r"/^P(?:\d+D)?(?:T(?:\d+H)?(?:\d+M)?(?:[\d.]+S)?)?$/",
+        "byte" | "base64" =>
+            r"/^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$/",
+        "base64url" => r"/^[\w-]*$/",
+        "hex" | "base16" => r"/^[0-9a-f]*$/i",
+        "e164" | "phone" => r"/^\+[1-9]\d{6,14}$/",
+        "ulid" => r"/^[0-9A-HJKMNP-TV-Z]{26}$/i",
+        "int64" => r"/^-?\d+$/",
+        "uint64" => r"/^\d+$/",
+        _ => return None,
+    })
+}
+
+/// The js functions decoding and encoding a
+/// string format, which is supported as a codec
+pub(crate) fn codec_functions(format: &str) -> Option<(&'static str, &'static str)> {
+    Some(match format {
+        "date-time" => ("s => new Date(s)", "d => d.toISOString()"),
+        "partial-date-time" => (
+            "s => new Date(s.endsWith('Z') ? s : s + 'Z')",
+            "d => d.toISOString().slice(0, -1)",
+        ),
+        "date" => ("s => new Date(s)", "d => d.toISOString().slice(0, 10)"),
+        // decoded into whole milliseconds, only days
+        // and smaller units are supported, the seconds
+        // are split to stay exact up to
+        // `Number.MAX_SAFE_INTEGER`
+        "duration" => (
+            r"s => { const [, d = 0, h = 0, m = 0, sec = '0'] = /P(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:([\d.]+)S)?)?/.exec(s); const [whole, fraction = ''] = sec.split('.'); return ((+d * 24 + +h) * 60 + +m) * 60000 + +whole * 1000 + Math.round(+('0.' + fraction) * 1000); }",
+            "ms => 'PT' + (ms - ms % 1000) / 1000 + (ms % 1000 ? '.' + String(ms % \
+             1000).padStart(3, '0').replace(/0+$/, '') : '') + 'S'",
+        ),
+        "byte" | "base64" => (
+            "s => Uint8Array.from(atob(s), c => c.charCodeAt(0))",
+            "b => btoa(Array.from(b, c => String.fromCharCode(c)).join(''))",
+        ),
+        "int64" | "uint64" => ("s => BigInt(s)", "n => n.toString()"),
+        _ => return None,
+    })
+}
+
+/// The js expression decoding the string `s` with
+/// a content encoding into text
+pub(crate) fn content_text(encoding: Option<&str>) -> Result<&'static str, Error> {
+    Ok(match encoding {
+        None => "s",
+        Some("base64") =>
+            "new TextDecoder().decode(Uint8Array.from(atob(s), c => c.charCodeAt(0)))",
+        Some("base64url") =>
+            "new TextDecoder().decode(Uint8Array.from(atob(s.replace(/-/g, \
+             '+').replace(/_/g, '/')), c => c.charCodeAt(0)))",
+        Some("hex" | "base16") =>
+            "new TextDecoder().decode(Uint8Array.from(s.match(/../g) ?? [], h => \
+             parseInt(h, 16)))",
+        Some(_) =>
+            return Err(Error::Unimplemented(
+                "Content: only the content encodings base64, base64url and hex are \
+                 supported",
+            )),
+    })
+}
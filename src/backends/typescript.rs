@@ -0,0 +1,487 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+use super::{AdditionalProperties, Backend, Js, Parameter, Property, js, js_doc};
+#[cfg(feature = "pretty")]
+use crate::PrettyConfig;
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+/// Outputs TypeScript types, without any runtime
+///
+/// As nothing transforms the values, the types
+/// describe the json input of the zod schemas,
+/// e.g. dates stay strings.
+pub struct TypeScriptBackend;
+
+impl Backend for TypeScriptBackend {
+    fn unknown(&self) -> JsResult { Ok(Js::from("unknown")) }
+
+    fn any(&self) -> JsResult { Ok(Js::from("any")) }
+
+    fn never(&self) -> JsResult { Ok(Js::from("never")) }
+
+    fn null(&self) -> JsResult { Ok(Js::from("null")) }
+
+    fn boolean(&self) -> JsResult { Ok(Js::from("boolean")) }
+
+    fn number(&self, _is_int: bool, _options: &NumberValidation) -> JsResult {
+        Ok(Js::from("number"))
+    }
+
+    fn is_format_supported(&self, _format: &str) -> bool { true }
+
+    fn string(&self, _format: Option<&str>, _options: &StringValidation) -> JsResult {
+        Ok(Js::from("string"))
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            // the schema outputs the same date
+            DateFormat::JsDate | DateFormat::DateToJson | DateFormat::CoerceDate =>
+                "Date",
+            DateFormat::IsoStringDate | DateFormat::Codec => "string",
+        }))
+    }
+
+    fn codec(&self, _format: &str) -> JsResult { Ok(Js::from("string")) }
+
+    fn content(&self, _encoding: Option<&str>, _inner: Js, _codec: bool) -> JsResult {
+        Ok(Js::from("string"))
+    }
+
+    fn literal(&self, value: &Value) -> JsResult {
+        Ok(serde_json::to_string(value)?.into())
+    }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult {
+        let mut converted = Vec::with_capacity(values.len());
+
+        for value in values {
+            converted.push(serde_json::to_string(value)?);
+        }
+
+        Ok(converted.join(" | ").into())
+    }
+
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        let mut object_inner = Vec::with_capacity(properties.len());
+
+        for property in properties {
+            // the input of a property with a default may
+            // be left out
+            let optional = if property.optional || property.default.is_some() {
+                "?"
+            } else {
+                ""
+            };
+
+            object_inner.push(js!(
+                format!(
+                    "{}{}{optional}: ",
+                    js_doc(property.description.as_deref()),
+                    serde_json::to_string(&property.key)?,
+                ),
+                property.schema,
+                ";",
+            ));
+        }
+
+        let object_parsed = if !object_inner.is_empty() {
+            Some(js!("{ ", Js::join(object_inner, " "), " }"))
+        } else {
+            None
+        };
+
+        Ok(match additional {
+            AdditionalProperties::Unset =>
+                object_parsed.unwrap_or_else(|| Js::from("Record<string, unknown>")),
+            AdditionalProperties::Forbidden =>
+                object_parsed.unwrap_or_else(|| Js::from("Record<string, never>")),
+            AdditionalProperties::Any(additional)
+            | AdditionalProperties::Schema(additional) => match object_parsed {
+                Some(object_parsed) => self.intersection(
+                    object_parsed,
+                    js!("Record<string, ", additional, ">"),
+                )?,
+                None => js!("Record<string, ", additional, ">"),
+            },
+        })
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        _dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        // the dependencies can't be typed
+        Ok(schema)
+    }
+
+    fn array(&self, items: Js, _options: &ArrayValidation) -> JsResult {
+        Ok(js!("Array<", items, ">"))
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        Ok(if let Some(rest) = rest {
+            js!("[", Js::join(items, ", "), ", ...Array<", rest, ">]")
+        } else {
+            js!("[", Js::join(items, ", "), "]")
+        })
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult { Ok(Js::join(variants, " | ")) }
+
+    fn discriminated_union(&self, _key: &str, variants: Vec<Js>) -> JsResult {
+        self.union(variants)
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        // `&` binds stronger than `|`
+        let wrap = |schema: Js| {
+            if schema.contains('|') {
+                js!("(", schema, ")")
+            } else {
+                schema
+            }
+        };
+
+        Ok(js!(wrap(left), " & ", wrap(right)))
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { Ok(js!(schema, " | null")) }
+
+    fn default(&self, schema: Js, _default: &Value, _prefault: bool) -> JsResult {
+        Ok(schema)
+    }
+
+    fn reference(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn lazy(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        self.intersection(
+            variant,
+            js!(
+                format!("{{ {}: ", serde_json::to_string(key)?),
+                self.literal(value)?,
+                " }",
+            ),
+        )
+    }
+
+    // types can reference a declaration before it
+    fn lazy_with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        self.with_discriminant(variant, key, value)
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult { Ok(name.to_owned()) }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        // there is nothing to validate with
+        Ok(format!("({value}) as {name}"))
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult { Ok(name.to_owned()) }
+
+    fn encode_value(&self, _name: &str, _value: &str) -> Option<String> { None }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> { None }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}type {name} = ",
+                js_doc(description),
+                if export { "export " } else { "" }
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    fn schema_type(&self, _output: &str, _input: &str) -> Option<String> { None }
+
+    fn generic(
+        &self,
+        name: &str,
+        parameters: &[Parameter],
+        schema: Js,
+        description: Option<&str>,
+        _typed: bool,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}type {name}<{}> = ",
+                js_doc(description),
+                if export { "export " } else { "" },
+                parameters
+                    .iter()
+                    .map(|parameter| parameter.type_name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        Ok(js!(format!("{name}<"), Js::join(arguments, ", "), ">"))
+    }
+
+    #[cfg(feature = "pretty")]
+    fn format(&self, parsed: &str, config: &PrettyConfig) -> ParserResult {
+        // a type on its own is not valid TypeScript
+        const PREFIX: &str = "type T =";
+
+        let formatted =
+            crate::pretty::format_js(&format!("{PREFIX} {parsed};"), ".ts", config)
+                .ok()
+                .ok_or(crate::Error::PrettifyError)?;
+
+        formatted
+            .strip_prefix(PREFIX)
+            .map(|formatted| formatted.trim_start().to_owned())
+            .ok_or(crate::Error::PrettifyError)
+    }
+}
+
+/// Outputs the TypeScript types of the output of
+/// the schemas, e.g. for a `.d.ts` file with
+/// codecs
+///
+/// Dates and codecs are typed decoded, and a
+/// property with a default is not optional.
+pub(crate) struct TypeScriptOutputBackend;
+
+impl Backend for TypeScriptOutputBackend {
+    fn unknown(&self) -> JsResult { TypeScriptBackend.unknown() }
+
+    fn any(&self) -> JsResult { TypeScriptBackend.any() }
+
+    fn never(&self) -> JsResult { TypeScriptBackend.never() }
+
+    fn null(&self) -> JsResult { TypeScriptBackend.null() }
+
+    fn boolean(&self) -> JsResult { TypeScriptBackend.boolean() }
+
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult {
+        TypeScriptBackend.number(is_int, options)
+    }
+
+    fn is_format_supported(&self, format: &str) -> bool {
+        TypeScriptBackend.is_format_supported(format)
+    }
+
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult {
+        TypeScriptBackend.string(format, options)
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            DateFormat::IsoStringDate => "string",
+            DateFormat::JsDate
+            | DateFormat::DateToJson
+            | DateFormat::CoerceDate
+            | DateFormat::Codec => "Date",
+        }))
+    }
+
+    fn codec(&self, format: &str) -> JsResult {
+        Ok(Js::from(match format {
+            "date-time" | "partial-date-time" | "date" => "Date",
+            "duration" => "number",
+            "byte" | "base64" => "Uint8Array",
+            "int64" | "uint64" => "bigint",
+            _ =>
+                return Err(Error::ForgotCheck(
+                    "Codec: parse_codec requires a format supported by is_codec",
+                )),
+        }))
+    }
+
+    // the json is parsed into its schema
+    fn content(&self, _encoding: Option<&str>, inner: Js, _codec: bool) -> JsResult {
+        Ok(inner)
+    }
+
+    fn literal(&self, value: &Value) -> JsResult { TypeScriptBackend.literal(value) }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult { TypeScriptBackend.r#enum(values) }
+
+    fn object(
+        &self,
+        mut properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        // the default is outputted for a missing
+        // property
+        for property in &mut properties {
+            if property.default.take().is_some() {
+                property.optional = false;
+            }
+        }
+
+        TypeScriptBackend.object(properties, additional)
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        TypeScriptBackend.dependent_required(schema, dependencies)
+    }
+
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult {
+        TypeScriptBackend.array(items, options)
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        TypeScriptBackend.tuple(items, rest)
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult { TypeScriptBackend.union(variants) }
+
+    fn discriminated_union(&self, key: &str, variants: Vec<Js>) -> JsResult {
+        TypeScriptBackend.discriminated_union(key, variants)
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        TypeScriptBackend.intersection(left, right)
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { TypeScriptBackend.nullable(schema) }
+
+    fn default(&self, schema: Js, default: &Value, prefault: bool) -> JsResult {
+        TypeScriptBackend.default(schema, default, prefault)
+    }
+
+    fn reference(&self, name: &str) -> JsResult { TypeScriptBackend.reference(name) }
+
+    fn lazy(&self, name: &str) -> JsResult { TypeScriptBackend.lazy(name) }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        TypeScriptBackend.with_discriminant(variant, key, value)
+    }
+
+    fn lazy_with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        TypeScriptBackend.lazy_with_discriminant(variant, key, value)
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult {
+        TypeScriptBackend.input_type(name)
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        TypeScriptBackend.parse_value(name, value)
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult {
+        TypeScriptBackend.output_type(name)
+    }
+
+    fn encode_value(&self, name: &str, value: &str) -> Option<String> {
+        TypeScriptBackend.encode_value(name, value)
+    }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> {
+        TypeScriptBackend.import()
+    }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        TypeScriptBackend.declaration(name, schema, description, export)
+    }
+
+    fn schema_type(&self, output: &str, input: &str) -> Option<String> {
+        TypeScriptBackend.schema_type(output, input)
+    }
+
+    fn generic(
+        &self,
+        name: &str,
+        parameters: &[Parameter],
+        schema: Js,
+        description: Option<&str>,
+        typed: bool,
+        export: bool,
+    ) -> JsResult {
+        TypeScriptBackend.generic(name, parameters, schema, description, typed, export)
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        TypeScriptBackend.instantiate(name, arguments)
+    }
+
+    #[cfg(feature = "pretty")]
+    fn format(&self, parsed: &str, config: &PrettyConfig) -> ParserResult {
+        TypeScriptBackend.format(parsed, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use schemars::{JsonSchema, schema::Schema};
+
+    use crate::{Config, Parser, Target, test_helpers::generator};
+
+    /// A user
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TestSchema {
+        /// The name of the user
+        name: String,
+        age: Option<u8>,
+        born: NaiveDate,
+        tags: Vec<TestEnum>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    enum TestEnum {
+        A,
+        B,
+    }
+
+    #[test]
+    fn test_typescript() {
+        let schema = generator().into_root_schema_for::<TestSchema>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::new(Config {
+            target: Target::TypeScript,
+            ..Default::default()
+        });
+        let result = parser.parse_declaration("User", &schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                "/** A user */\nexport type User = { \"age\"?: number | null; ",
+                "\"born\": Date; /** The name of the user */\n\"name\": string; ",
+                "\"tags\": Array<\"A\" | \"B\">; };",
+            ),
+            &result
+        );
+        crate::parsers::check_types("typescript", &result);
+    }
+}
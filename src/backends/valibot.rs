@@ -0,0 +1,550 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+use super::{
+    AdditionalProperties,
+    Backend,
+    Js,
+    Property,
+    codec_functions,
+    content_text,
+    dependent_required_check,
+    format_regex,
+    js,
+    js_doc,
+};
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+/// Outputs valibot schemas
+///
+/// Valibot has no codecs, so codecs and embedded
+/// json are only decoded.
+pub struct ValibotBackend;
+
+impl ValibotBackend {
+    /// The valibot action validating a string
+    /// format, `None` if only `v.string()` is needed
+    fn format_action(format: &str) -> Option<Option<String>> {
+        Some(Some(String::from(match format {
+            "email" => "v.email()",
+            "idn-email" => "v.rfcEmail()",
+            "uri" | "iri" => "v.url()",
+            "uuid" => "v.uuid()",
+            "ipv4" => "v.ipv4()",
+            "ipv6" => "v.ipv6()",
+            "mac" => "v.mac()",
+            "date-time" => "v.isoTimestamp()",
+            "date" => "v.isoDate()",
+            "byte" | "base64" => "v.base64()",
+            "hex" | "base16" => "v.hexadecimal()",
+            "ulid" => "v.ulid()",
+            "cuid2" => "v.cuid2()",
+            "nanoid" => "v.nanoid()",
+            "emoji" => "v.emoji()",
+            "regex" =>
+                "v.check(s => { try { new RegExp(s); return true; } catch { return \
+                 false; } })",
+            // there is no validator in valibot
+            "uri-reference"
+            | "iri-reference"
+            | "uri-template"
+            | "idn-hostname"
+            | "json-pointer"
+            | "relative-json-pointer" => return Some(None),
+            _ =>
+                return format_regex(format)
+                    .map(|regex| Some(format!("v.regex({regex})"))),
+        })))
+    }
+
+    /// Pipe a schema through its actions
+    fn pipe(schema: Js, actions: Vec<String>) -> Js {
+        if actions.is_empty() {
+            schema
+        } else {
+            js!("v.pipe(", schema, format!(", {})", actions.join(", ")))
+        }
+    }
+}
+
+impl Backend for ValibotBackend {
+    fn unknown(&self) -> JsResult { Ok(Js::from("v.unknown()")) }
+
+    fn any(&self) -> JsResult { Ok(Js::from("v.any()")) }
+
+    fn never(&self) -> JsResult { Ok(Js::from("v.never()")) }
+
+    fn null(&self) -> JsResult { Ok(Js::from("v.null()")) }
+
+    fn boolean(&self) -> JsResult { Ok(Js::from("v.boolean()")) }
+
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult {
+        let mut actions = Vec::new();
+
+        if is_int {
+            actions.push(String::from("v.integer()"));
+        }
+
+        if let Some(multiple_of) = options.multiple_of {
+            actions.push(format!("v.multipleOf({multiple_of})"));
+        }
+
+        if let Some(val) = options.minimum {
+            actions.push(format!("v.minValue({val})"));
+        }
+
+        if let Some(val) = options.exclusive_minimum {
+            actions.push(format!("v.gtValue({val})"));
+        }
+
+        if let Some(val) = options.maximum {
+            actions.push(format!("v.maxValue({val})"));
+        }
+
+        if let Some(val) = options.exclusive_maximum {
+            actions.push(format!("v.ltValue({val})"));
+        }
+
+        Ok(Self::pipe(Js::from("v.number()"), actions))
+    }
+
+    fn is_format_supported(&self, format: &str) -> bool {
+        Self::format_action(format).is_some()
+    }
+
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult {
+        let mut actions = Vec::new();
+
+        if let Some(action) = format.and_then(Self::format_action).flatten() {
+            actions.push(action);
+        }
+
+        if let Some(min_length) = options.min_length
+            && let Some(max_length) = options.max_length
+            && min_length == max_length
+        {
+            actions.push(format!("v.length({min_length})"));
+        } else {
+            if let Some(min_length) = options.min_length {
+                actions.push(format!("v.minLength({min_length})"));
+            }
+
+            if let Some(max_length) = options.max_length {
+                actions.push(format!("v.maxLength({max_length})"));
+            }
+        }
+
+        if let Some(pattern) = &options.pattern {
+            actions.push(format!(
+                "v.regex(new RegExp({}))",
+                serde_json::to_string(&pattern)?
+            ));
+        }
+
+        Ok(Self::pipe(Js::from("v.string()"), actions))
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            DateFormat::CoerceDate =>
+                "v.pipe(v.union([v.string(), v.number(), v.date()]), v.transform(d => \
+                 new Date(d)), v.date())",
+            DateFormat::JsDate => "v.date()",
+            DateFormat::DateToJson =>
+                "v.pipe(v.date(), v.check(date => date.toJSON() !== null))",
+            DateFormat::IsoStringDate | DateFormat::Codec =>
+                return Err(Error::ForgotCheck(
+                    "Date: the date format has to turn the string into a Date",
+                )),
+        }))
+    }
+
+    fn codec(&self, format: &str) -> JsResult {
+        let (Some(Some(input)), Some((decode, _))) =
+            (Self::format_action(format), codec_functions(format))
+        else {
+            return Err(Error::ForgotCheck(
+                "Codec: parse_codec requires a format supported by is_codec",
+            ));
+        };
+
+        Ok(format!("v.pipe(v.string(), {input}, v.transform({decode}))").into())
+    }
+
+    fn content(&self, encoding: Option<&str>, inner: Js, _codec: bool) -> JsResult {
+        let text = content_text(encoding)?;
+        let input = self.string(encoding, &Default::default())?;
+
+        Ok(js!(
+            "v.pipe(",
+            input,
+            format!(
+                ", v.rawTransform(({{ dataset: {{ value: s }}, addIssue, NEVER }}) => \
+                 {{ try {{ return JSON.parse({text}); }} catch {{ addIssue({{ message: \
+                 'Invalid JSON' }}); return NEVER; }} }}), "
+            ),
+            inner,
+            ")",
+        ))
+    }
+
+    fn literal(&self, value: &Value) -> JsResult {
+        Ok(match value {
+            Value::Null => Js::from("v.null()"),
+            Value::Array(_) | Value::Object(_) =>
+                return Err(Error::Unimplemented(
+                    "Literal: only primitive literals are supported",
+                )),
+            _ => format!("v.literal({})", serde_json::to_string(value)?).into(),
+        })
+    }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult {
+        // a picklist only holds strings, numbers and
+        // booleans
+        if values.iter().all(|value| {
+            matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_))
+        }) {
+            let mut converted = Vec::with_capacity(values.len());
+
+            for value in values {
+                converted.push(serde_json::to_string(value)?);
+            }
+
+            return Ok(format!("v.picklist([{}])", converted.join(", ")).into());
+        }
+
+        let mut converted = Vec::with_capacity(values.len());
+
+        for value in values {
+            converted.push(self.literal(value)?);
+        }
+
+        self.union(converted)
+    }
+
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        let mut object_inner = Vec::with_capacity(properties.len());
+
+        for property in properties {
+            let schema = if property.optional && property.default.is_none() {
+                match property.schema.strip_wrapper("v.nullable(", ")") {
+                    Ok(schema) => js!("v.nullish(", schema, ")"),
+                    Err(schema) => js!("v.optional(", schema, ")"),
+                }
+            } else {
+                property.schema
+            };
+
+            let key = serde_json::to_string(&property.key)?;
+
+            object_inner.push(js!(format!("{key}: "), schema));
+        }
+
+        let object_inner_parsed = if !object_inner.is_empty() {
+            Some(Js::join(object_inner, ", "))
+        } else {
+            None
+        };
+
+        Ok(match additional {
+            AdditionalProperties::Unset => object_inner_parsed
+                .map(|p| js!("v.object({ ", p, " })"))
+                .unwrap_or_else(|| Js::from("v.looseObject({})")),
+            AdditionalProperties::Forbidden => match object_inner_parsed {
+                Some(p) => js!("v.object({ ", p, " })"),
+                None =>
+                    return Err(Error::Unimplemented(
+                        "Object: additional_properties are false, and there are no \
+                         properties given",
+                    )),
+            },
+            AdditionalProperties::Any(additional)
+            | AdditionalProperties::Schema(additional) => match object_inner_parsed {
+                Some(p) => js!("v.objectWithRest({ ", p, " }, ", additional, ")"),
+                None => js!("v.record(v.string(), ", additional, ")"),
+            },
+        })
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        Ok(Self::pipe(schema, vec![format!(
+            "v.check({})",
+            dependent_required_check(dependencies)?
+        )]))
+    }
+
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult {
+        let mut actions = Vec::new();
+
+        if let Some(min_items) = options.min_items {
+            actions.push(format!("v.minLength({min_items})"));
+        }
+
+        if let Some(max_items) = options.max_items {
+            actions.push(format!("v.maxLength({max_items})"));
+        }
+
+        Ok(Self::pipe(js!("v.array(", items, ")"), actions))
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        Ok(if let Some(rest) = rest {
+            js!("v.tupleWithRest([", Js::join(items, ", "), "], ", rest, ")")
+        } else {
+            js!("v.strictTuple([", Js::join(items, ", "), "])")
+        })
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult {
+        Ok(js!("v.union([", Js::join(variants, ", "), "])"))
+    }
+
+    fn discriminated_union(&self, key: &str, variants: Vec<Js>) -> JsResult {
+        Ok(js!(
+            format!("v.variant({}, [", serde_json::to_string(key)?),
+            Js::join(variants, ", "),
+            "])",
+        ))
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        Ok(js!("v.intersect([", left, ", ", right, "])"))
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { Ok(js!("v.nullable(", schema, ")")) }
+
+    fn default(&self, schema: Js, default: &Value, _prefault: bool) -> JsResult {
+        // valibot always parses the default, like
+        // `z.prefault`
+        Ok(js!(
+            "v.optional(",
+            schema,
+            format!(", {})", serde_json::to_string(default)?),
+        ))
+    }
+
+    fn reference(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn lazy(&self, name: &str) -> JsResult { Ok(format!("v.lazy(() => {name})").into()) }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        // the variant validates the whole input after
+        // its discriminant, so it can be piped or lazy
+        Ok(js!(
+            format!("v.pipe(v.looseObject({{ {}: ", serde_json::to_string(key)?),
+            self.literal(value)?,
+            " }), ",
+            variant,
+            ")",
+        ))
+    }
+
+    fn lazy_with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        self.with_discriminant(js!("v.lazy(() => ", variant, ")"), key, value)
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult {
+        Ok(format!("v.InferInput<typeof {name}>"))
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        Ok(format!("v.parse({name}, {value})"))
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult {
+        Ok(format!("v.InferOutput<typeof {name}>"))
+    }
+
+    // the transforms of valibot only decode
+    fn encode_value(&self, _name: &str, _value: &str) -> Option<String> { None }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> {
+        Some(("* as v", "valibot"))
+    }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}const {name} = ",
+                js_doc(description),
+                if export { "export " } else { "" }
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    fn schema_type(&self, output: &str, input: &str) -> Option<String> {
+        Some(match output == input {
+            true => format!("v.GenericSchema<{input}>"),
+            false => format!("v.GenericSchema<{input}, {output}>"),
+        })
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        Ok(js!(format!("{name}("), Js::join(arguments, ", "), ")"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema::Schema;
+    use serde_json::json;
+
+    use crate::{Config, Parser, Target};
+
+    #[test]
+    fn test_valibot() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "required": ["email", "shape"],
+            "properties": {
+                "email": { "type": "string", "format": "email", "maxLength": 64 },
+                "age": { "type": ["integer", "null"], "minimum": 0 },
+                "shape": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "required": ["type", "radius"],
+                            "properties": {
+                                "type": { "type": "string", "enum": ["circle"] },
+                                "radius": { "type": "number" },
+                            },
+                        },
+                        {
+                            "type": "object",
+                            "required": ["type"],
+                            "properties": {
+                                "type": { "type": "string", "enum": ["square"] },
+                                "sides": { "type": "array", "items": { "type": "number" }, "default": [] },
+                            },
+                        },
+                    ],
+                },
+            },
+        }))
+        .unwrap();
+
+        let parser = Parser::new(Config {
+            target: Target::Valibot,
+            ..Default::default()
+        });
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"v.object({ "age": v.nullish(v.pipe(v.number(), v.integer(), "#,
+                r#"v.minValue(0))), "email": v.pipe(v.string(), v.email(), "#,
+                r#"v.maxLength(64)), "shape": v.variant("type", [v.object({ "#,
+                r#""radius": v.number(), "type": v.literal("circle") }), "#,
+                r#"v.object({ "sides": v.optional(v.array(v.number()), []), "#,
+                r#""type": v.literal("square") })]) })"#,
+            ),
+            &result
+        );
+    }
+
+    #[test]
+    fn test_valibot_discriminator() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Dog" },
+                            { "$ref": "#/components/schemas/Cat" },
+                        ],
+                        "discriminator": {
+                            "propertyName": "petType",
+                            "mapping": {
+                                "dog": "#/components/schemas/Dog",
+                                "cat": "#/components/schemas/Cat",
+                            },
+                        },
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {
+                            "petType": { "type": "string" },
+                            "owner": { "type": "string" },
+                            "leash": { "type": "string" },
+                        },
+                        "dependentRequired": { "owner": ["leash"] },
+                    },
+                    "Cat": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {
+                            "petType": { "type": "string" },
+                            "friend": { "$ref": "#/components/schemas/Pet" },
+                        },
+                    },
+                },
+            },
+        });
+
+        let parser = Parser::new(Config {
+            target: Target::Valibot,
+            ..Default::default()
+        });
+        let result = parser.parse_openapi(&document).unwrap();
+
+        assert!(result.contains(concat!(
+            r#"export const Pet = v.variant("petType", [v.pipe(v.looseObject("#,
+            r#"{ "petType": v.literal("dog") }), Dog), v.pipe(v.looseObject("#,
+            r#"{ "petType": v.literal("cat") }), Cat)]);"#,
+        )));
+        // the check of the piped dog still validates it
+        crate::parsers::check_module_with(
+            crate::VALIBOT_IMPORT,
+            &format!(
+                concat!(
+                    "{}",
+                    "v.parse(Pet, {{ petType: 'cat', friend: {{ petType: 'dog' }} }});\n",
+                    "if (v.safeParse(Pet, {{ petType: 'dog', owner: 'me' }}).success) ",
+                    "throw new Error('leash');\n",
+                ),
+                result
+            ),
+        );
+
+        // the variant of a cycle is declared after the
+        // union of its file
+        let files = parser
+            .parse_openapi_files(&document, |name| match name {
+                "Cat" => String::from("cats"),
+                _ => String::from("pets"),
+            })
+            .unwrap();
+
+        assert!(files["pets.ts"].contains(concat!(
+            r#"v.pipe(v.looseObject({ "petType": v.literal("cat") }), "#,
+            "v.lazy(() => Cat))]);",
+        )));
+        crate::parsers::check_module_with(
+            crate::VALIBOT_IMPORT,
+            &format!(
+                "{}{}v.parse(Pet, {{ petType: 'cat', friend: {{ petType: 'dog' }} }});\n",
+                files["pets.ts"], files["cats.ts"],
+            ),
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::{ArrayValidation, NumberValidation, StringValidation};
+use serde_json::Value;
+
+use super::{
+    AdditionalProperties,
+    Backend,
+    Js,
+    Property,
+    codec_functions,
+    content_text,
+    dependent_required_check,
+    format_regex,
+    js,
+    js_doc,
+};
+use crate::{DateFormat, Error, JsResult, ParserResult};
+
+/// Outputs zod/mini schemas
+pub struct ZodBackend;
+
+impl ZodBackend {
+    /// The zod function validating a string format
+    fn format_function(format: &str) -> Option<&'static str> {
+        Some(match format {
+            "email" => "z.email()",
+            "idn-email" => "z.email({ pattern: z.regexes.unicodeEmail })",
+            "uri" | "iri" => "z.url()",
+            "uuid" => "z.guid()",
+            "ipv4" => "z.ipv4()",
+            "ipv6" => "z.ipv6()",
+            "cidrv4" | "ipv4-cidr" => "z.cidrv4()",
+            "cidrv6" | "ipv6-cidr" => "z.cidrv6()",
+            "mac" => "z.mac()",
+            "hostname" => "z.hostname()",
+            "date-time" => "z.iso.datetime({ offset: true })",
+            // naive date times don't have an offset
+            "partial-date-time" => "z.iso.datetime({ local: true })",
+            "date" => "z.iso.date()",
+            "time" => "z.iso.time()",
+            "duration" => "z.iso.duration()",
+            "byte" | "base64" => "z.base64()",
+            "base64url" => "z.base64url()",
+            "hex" | "base16" => "z.hex()",
+            "e164" | "phone" => "z.e164()",
+            "jwt" => "z.jwt()",
+            "ulid" => "z.ulid()",
+            "cuid" => "z.cuid()",
+            "cuid2" => "z.cuid2()",
+            "nanoid" => "z.nanoid()",
+            "ksuid" => "z.ksuid()",
+            "xid" => "z.xid()",
+            "emoji" => "z.emoji()",
+            // validated with a check, or there is no
+            // validator in zod
+            "regex"
+            | "int64"
+            | "uint64"
+            | "uri-reference"
+            | "iri-reference"
+            | "uri-template"
+            | "idn-hostname"
+            | "json-pointer"
+            | "relative-json-pointer" => "z.string()",
+            _ => return None,
+        })
+    }
+}
+
+impl Backend for ZodBackend {
+    fn unknown(&self) -> JsResult { Ok(Js::from("z.unknown()")) }
+
+    fn any(&self) -> JsResult { Ok(Js::from("z.any()")) }
+
+    fn never(&self) -> JsResult { Ok(Js::from("z.never()")) }
+
+    fn null(&self) -> JsResult { Ok(Js::from("z.null()")) }
+
+    fn boolean(&self) -> JsResult { Ok(Js::from("z.boolean()")) }
+
+    fn number(&self, is_int: bool, options: &NumberValidation) -> JsResult {
+        let mut res = if is_int {
+            if options.minimum.is_some_and(|val| val == 0.) {
+                String::from("z.uint32()")
+            } else {
+                String::from("z.int32()")
+            }
+        } else {
+            String::from("z.float64()")
+        };
+
+        let mut checks = Vec::new();
+
+        if let Some(multiple_of) = options.multiple_of {
+            checks.push(format!("z.step({multiple_of})"));
+        }
+
+        if let Some(val) = options.minimum
+            && (val != 0. || !is_int)
+        {
+            checks.push(format!("z.minimum({val})"));
+        }
+
+        if let Some(val) = options.exclusive_minimum {
+            checks.push(format!("z.gt({val})"));
+        }
+
+        if let Some(val) = options.maximum {
+            checks.push(format!("z.maximum({val})"));
+        }
+
+        if let Some(val) = options.exclusive_maximum {
+            checks.push(format!("z.lt({val})"));
+        }
+
+        if !checks.is_empty() {
+            res.push_str(&format!(".check({})", checks.join(", ")));
+        }
+
+        Ok(res.into())
+    }
+
+    fn is_format_supported(&self, format: &str) -> bool {
+        Self::format_function(format).is_some()
+    }
+
+    fn string(&self, format: Option<&str>, options: &StringValidation) -> JsResult {
+        let mut res = String::from(
+            format
+                .and_then(Self::format_function)
+                .unwrap_or("z.string()"),
+        );
+
+        let mut checks = Vec::new();
+
+        match format {
+            Some("regex") => checks.push(String::from(
+                "z.refine(s => { try { new RegExp(s); return true; } catch { return \
+                 false; } })",
+            )),
+            Some("int64") => checks.push(String::from(r"z.regex(/^-?\d+$/)")),
+            Some("uint64") => checks.push(String::from(r"z.regex(/^\d+$/)")),
+            _ => {},
+        }
+
+        if let Some(min_length) = options.min_length
+            && let Some(max_length) = options.max_length
+            && min_length == max_length
+        {
+            checks.push(format!("z.length({min_length})"));
+        } else {
+            if let Some(min_items) = options.min_length {
+                checks.push(format!("z.minLength({min_items})"));
+            }
+
+            if let Some(max_items) = options.max_length {
+                checks.push(format!("z.maxLength({max_items})"));
+            }
+        }
+
+        if let Some(pattern) = &options.pattern {
+            checks.push(format!(
+                "z.regex(new RegExp({}))",
+                serde_json::to_string(&pattern)?
+            ));
+        }
+
+        if !checks.is_empty() {
+            res.push_str(&format!(".check({})", checks.join(", ")));
+        }
+
+        Ok(res.into())
+    }
+
+    fn date(&self, date_format: DateFormat, _format: &str) -> JsResult {
+        Ok(Js::from(match date_format {
+            DateFormat::CoerceDate => "z.coerce.date()",
+            DateFormat::JsDate => "z.date()",
+            DateFormat::DateToJson => "z.date().check(z.refine(date => date.toJSON()))",
+            DateFormat::IsoStringDate | DateFormat::Codec =>
+                return Err(Error::ForgotCheck(
+                    "Date: the date format has to turn the string into a Date",
+                )),
+        }))
+    }
+
+    fn codec(&self, format: &str) -> JsResult {
+        let Some((decode, encode)) = codec_functions(format) else {
+            return Err(Error::ForgotCheck(
+                "Codec: parse_codec requires a format supported by is_codec",
+            ));
+        };
+
+        let (input, output) = match format {
+            "date-time" => ("z.iso.datetime({ offset: true })".into(), "z.date()"),
+            "partial-date-time" => ("z.iso.datetime({ local: true })".into(), "z.date()"),
+            "date" => ("z.iso.date()".into(), "z.date()"),
+            // z.iso.duration() accepts years, months and
+            // weeks, which the decoder doesn't support
+            "duration" => {
+                let Some(regex) = format_regex(format) else {
+                    return Err(Error::ForgotCheck(
+                        "Codec: the duration format has to have a regex",
+                    ));
+                };
+
+                (
+                    format!("z.string().check(z.regex({regex}))"),
+                    "z.int().check(z.nonnegative())",
+                )
+            },
+            "byte" | "base64" => ("z.base64()".into(), "z.instanceof(Uint8Array)"),
+            "int64" => (r"z.string().check(z.regex(/^-?\d+$/))".into(), "z.bigint()"),
+            "uint64" => (
+                r"z.string().check(z.regex(/^\d+$/))".into(),
+                "z.bigint().check(z.nonnegative())",
+            ),
+            _ =>
+                return Err(Error::ForgotCheck(
+                    "Codec: parse_codec requires a format supported by is_codec",
+                )),
+        };
+
+        Ok(format!(
+            "z.codec({input}, {output}, {{ decode: {decode}, encode: {encode} }})"
+        )
+        .into())
+    }
+
+    fn content(&self, encoding: Option<&str>, inner: Js, codec: bool) -> JsResult {
+        let text = content_text(encoding)?;
+        let (input, encode) = match encoding {
+            Some("base64") => (
+                "z.base64()",
+                "btoa(Array.from(new TextEncoder().encode(JSON.stringify(v)), c => \
+                 String.fromCharCode(c)).join(''))",
+            ),
+            Some("base64url") => (
+                "z.base64url()",
+                "btoa(Array.from(new TextEncoder().encode(JSON.stringify(v)), c => \
+                 String.fromCharCode(c)).join('')).replace(/\\+/g, '-').replace(/\\//g, \
+                 '_').replace(/=+$/, '')",
+            ),
+            Some("hex" | "base16") => (
+                "z.hex()",
+                "Array.from(new TextEncoder().encode(JSON.stringify(v)), c => \
+                 c.toString(16).padStart(2, '0')).join('')",
+            ),
+            _ => ("z.string()", "JSON.stringify(v)"),
+        };
+
+        let decode = format!(
+            "(s, ctx) => {{ try {{ return JSON.parse({text}); }} catch {{ \
+             ctx.issues.push({{ code: 'custom', message: 'Invalid JSON', input: s }}); \
+             return z.NEVER; }} }}"
+        );
+
+        Ok(if codec {
+            js!(
+                format!("z.codec({input}, "),
+                inner,
+                format!(", {{ decode: {decode}, encode: v => {encode} }})"),
+            )
+        } else {
+            js!(
+                format!("z.pipe(z.pipe({input}, z.transform({decode})), "),
+                inner,
+                ")",
+            )
+        })
+    }
+
+    fn literal(&self, value: &Value) -> JsResult {
+        Ok(format!("z.literal({})", serde_json::to_string(value)?).into())
+    }
+
+    fn r#enum(&self, values: &[Value]) -> JsResult {
+        let mut converted = Vec::with_capacity(values.len());
+
+        for value in values {
+            converted.push(serde_json::to_string(value)?);
+        }
+
+        Ok(format!("z.enum([{}])", converted.join(", ")).into())
+    }
+
+    fn object(
+        &self,
+        properties: Vec<Property>,
+        additional: AdditionalProperties,
+    ) -> JsResult {
+        let mut object_inner = Vec::with_capacity(properties.len());
+
+        for property in properties {
+            let schema = if property.optional && property.default.is_none() {
+                match property.schema.strip_wrapper("z.nullable(", ")") {
+                    Ok(schema) => js!("z.nullish(", schema, ")"),
+                    Err(schema) => js!("z.optional(", schema, ")"),
+                }
+            } else {
+                property.schema
+            };
+
+            let key = serde_json::to_string(&property.key)?;
+
+            object_inner.push(js!(format!("{key}: "), schema));
+        }
+
+        let object_inner_parsed = if !object_inner.is_empty() {
+            Some(Js::join(object_inner, ", "))
+        } else {
+            None
+        };
+
+        Ok(match additional {
+            AdditionalProperties::Unset => object_inner_parsed
+                .map(|p| js!("z.object({ ", p, " })"))
+                .unwrap_or_else(|| Js::from("z.looseObject({})")),
+            AdditionalProperties::Forbidden => match object_inner_parsed {
+                Some(p) => js!("z.object({ ", p, " })"),
+                None =>
+                    return Err(Error::Unimplemented(
+                        "Object: additional_properties are false, and there are no \
+                         properties given",
+                    )),
+            },
+            AdditionalProperties::Any(additional) => match object_inner_parsed {
+                Some(p) =>
+                    js!("z.catchall(z.strictObject({ ", p, " }), ", additional, ")"),
+                None => js!("z.record(z.string(), ", additional, ")"),
+            },
+            AdditionalProperties::Schema(additional) => match object_inner_parsed {
+                Some(p) => js!("z.catchall(z.object({ ", p, " }), ", additional, ")"),
+                None => js!("z.record(z.string(), ", additional, ")"),
+            },
+        })
+    }
+
+    fn dependent_required(
+        &self,
+        schema: Js,
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> JsResult {
+        Ok(js!(
+            schema,
+            format!(
+                ".check(z.refine({}))",
+                dependent_required_check(dependencies)?
+            ),
+        ))
+    }
+
+    fn array(&self, items: Js, options: &ArrayValidation) -> JsResult {
+        let mut res = js!("z.array(", items, ")");
+        let mut checks = Vec::new();
+
+        if let Some(min_items) = options.min_items {
+            checks.push(format!("z.minLength({min_items})"));
+        }
+
+        if let Some(max_items) = options.max_items {
+            checks.push(format!("z.maxLength({max_items})"));
+        }
+
+        if !checks.is_empty() {
+            res = js!(res, format!(".check({})", checks.join(", ")));
+        }
+
+        Ok(res)
+    }
+
+    fn tuple(&self, items: Vec<Js>, rest: Option<Js>) -> JsResult {
+        Ok(if let Some(rest) = rest {
+            js!("z.tuple([", Js::join(items, ", "), "], ", rest, ")")
+        } else {
+            js!("z.tuple([", Js::join(items, ", "), "])")
+        })
+    }
+
+    fn union(&self, variants: Vec<Js>) -> JsResult {
+        Ok(js!("z.union([", Js::join(variants, ", "), "])"))
+    }
+
+    fn discriminated_union(&self, key: &str, variants: Vec<Js>) -> JsResult {
+        Ok(js!(
+            format!("z.discriminatedUnion({}, [", serde_json::to_string(key)?),
+            Js::join(variants, ", "),
+            "])",
+        ))
+    }
+
+    fn intersection(&self, left: Js, right: Js) -> JsResult {
+        Ok(js!("z.intersection(", left, ", ", right, ")"))
+    }
+
+    fn nullable(&self, schema: Js) -> JsResult { Ok(js!("z.nullable(", schema, ")")) }
+
+    fn default(&self, schema: Js, default: &Value, prefault: bool) -> JsResult {
+        let default = serde_json::to_string(default)?;
+
+        Ok(if prefault {
+            js!("z.prefault(", schema, format!(", {default})"))
+        } else {
+            js!("z._default(", schema, format!(", {default})"))
+        })
+    }
+
+    fn reference(&self, name: &str) -> JsResult { Ok(Js::from(name.to_owned())) }
+
+    fn lazy(&self, name: &str) -> JsResult { Ok(format!("z.lazy(() => {name})").into()) }
+
+    fn with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        Ok(js!(
+            "z.extend(",
+            variant,
+            format!(", {{ {}: ", serde_json::to_string(key)?),
+            self.literal(value)?,
+            " })",
+        ))
+    }
+
+    fn lazy_with_discriminant(&self, variant: Js, key: &str, value: &Value) -> JsResult {
+        // the discriminated union finds the values of
+        // a lazy variant when it first parses
+        Ok(js!(
+            "z.lazy(() => ",
+            self.with_discriminant(variant, key, value)?,
+            ")"
+        ))
+    }
+
+    fn input_type(&self, name: &str) -> ParserResult {
+        Ok(format!("z.input<typeof {name}>"))
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> ParserResult {
+        Ok(format!("{name}.parse({value})"))
+    }
+
+    fn output_type(&self, name: &str) -> ParserResult {
+        Ok(format!("z.output<typeof {name}>"))
+    }
+
+    fn encode_value(&self, name: &str, value: &str) -> Option<String> {
+        Some(format!("z.encode({name}, {value})"))
+    }
+
+    fn import(&self) -> Option<(&'static str, &'static str)> {
+        Some(("* as z", "zod/mini"))
+    }
+
+    fn declaration(
+        &self,
+        name: &str,
+        schema: Js,
+        description: Option<&str>,
+        export: bool,
+    ) -> JsResult {
+        Ok(js!(
+            format!(
+                "{}{}const {name} = ",
+                js_doc(description),
+                if export { "export " } else { "" }
+            ),
+            schema,
+            ";",
+        ))
+    }
+
+    fn schema_type(&self, output: &str, input: &str) -> Option<String> {
+        Some(match output == input {
+            true => format!("z.ZodMiniType<{output}>"),
+            false => format!("z.ZodMiniType<{output}, {input}>"),
+        })
+    }
+
+    fn instantiate(&self, name: &str, arguments: Vec<Js>) -> JsResult {
+        Ok(js!(format!("{name}("), Js::join(arguments, ", "), ")"))
+    }
+}
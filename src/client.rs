@@ -0,0 +1,491 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::Schema;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    DateFormat,
+    Parser,
+    ParserResult,
+    module::{Module, identifier},
+};
+
+/// The methods of an OpenAPI path item
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// The names of the helpers of a client
+const CLIENT_HELPER_NAMES: [&str; 2] = ["clientConfig", "searchParams"];
+
+/// The error of a declaration of a client, which
+/// has the name of another declaration
+const COLLISION: &str =
+    "Client: a declaration of an operation has the name of another declaration";
+
+/// The helpers shared by the operations of a
+/// client
+const CLIENT_HELPERS: &str = r#"export const clientConfig: { baseUrl: string; init: RequestInit & { headers?: Record<string, string> } } = { baseUrl: BASE_URL, init: {} };
+
+function searchParams(query: Record<string, unknown>): string {
+  const search = new URLSearchParams();
+  for (const [key, value] of Object.entries(query)) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item !== undefined && item !== null) search.append(key, String(item));
+    }
+  }
+  const result = search.toString();
+  return result ? `?${result}` : "";
+}"#;
+
+/// Follow the `$ref` of a parameter, request body
+/// or response to the components
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+
+    // a limit, so cyclic references end
+    for _ in 0..16 {
+        match value
+            .get("$ref")
+            .and_then(|reference| reference.as_str())
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+
+    value
+}
+
+/// The json schema of the content of a request
+/// body or response
+fn json_content(value: &Value) -> Option<&Value> {
+    value
+        .get("content")?
+        .as_object()?
+        .iter()
+        .find(|(media_type, _)| {
+            media_type.starts_with("application/json") || media_type.ends_with("+json")
+        })
+        .and_then(|(_, content)| content.get("schema"))
+}
+
+impl Parser {
+    /// Parse an OpenAPI 3.0 or 3.1 document into a
+    /// module with the schemas of the components,
+    /// and a `fetch` based function for each
+    /// operation of the paths
+    ///
+    /// Arguments are typed with the input of the
+    /// schemas, responses are parsed with them, so
+    /// the date format of the config also applies.
+    /// With codecs, request bodies are typed with
+    /// the output and encoded by their schema.
+    pub fn parse_openapi_client(&self, document: &Value) -> ParserResult {
+        self.0.output.require_typescript(
+            "Client: a client can only be outputted as a .ts module",
+        )?;
+        let base_url = document
+            .pointer("/servers/0/url")
+            .and_then(|url| url.as_str())
+            .unwrap_or("");
+
+        self.parse_module(&self.openapi_schemas(document)?, |output, module| {
+            for helper in CLIENT_HELPER_NAMES {
+                module.declare(String::from(helper), COLLISION)?;
+            }
+
+            output.push('\n');
+            output.push_str(
+                &CLIENT_HELPERS.replace("BASE_URL", &serde_json::to_string(base_url)?),
+            );
+            output.push('\n');
+
+            let Some(paths) = document.get("paths").and_then(|paths| paths.as_object())
+            else {
+                return Ok(());
+            };
+
+            for (path, item) in paths {
+                let item = resolve(document, item);
+
+                for method in METHODS {
+                    if let Some(operation) = item.get(method) {
+                        output.push('\n');
+                        output.push_str(&self.parse_operation(
+                            document, module, path, method, item, operation,
+                        )?);
+                        output.push('\n');
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Parse one operation into the declarations of
+    /// its schemas and its function
+    fn parse_operation(
+        &self,
+        document: &Value,
+        module: &mut Module,
+        path: &str,
+        method: &str,
+        item: &Value,
+        operation: &Value,
+    ) -> ParserResult {
+        let name = match operation.get("operationId").and_then(|id| id.as_str()) {
+            Some(id) => identifier(id),
+            None => identifier(&format!("{method}{}", path.replace(['{', '}'], ""))),
+        };
+        let name = module.declare(name, COLLISION)?;
+        let mut type_name = name.clone();
+        if let Some(first) = type_name.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+
+        // parameters of the operation replace the ones
+        // of the path with the same name
+        let mut parameters = BTreeMap::new();
+        for parameter in [item, operation]
+            .into_iter()
+            .filter_map(|value| value.get("parameters")?.as_array())
+            .flatten()
+        {
+            let parameter = resolve(document, parameter);
+
+            if let (Some(location), Some(key)) = (
+                parameter.get("in").and_then(|location| location.as_str()),
+                parameter.get("name").and_then(|key| key.as_str()),
+            ) {
+                parameters.insert((location, key), parameter);
+            }
+        }
+
+        let mut declaration_name = |suffix: &str| {
+            module.declare(
+                self.0.naming.name(&format!("{type_name}{suffix}")),
+                COLLISION,
+            )
+        };
+
+        let mut declarations = Vec::new();
+        let mut arguments = Vec::new();
+
+        for (location, suffix) in [("path", "Path"), ("query", "Query")] {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+
+            for ((_, key), parameter) in
+                parameters.iter().filter(|((l, _), _)| *l == location)
+            {
+                properties.insert(
+                    String::from(*key),
+                    parameter
+                        .get("schema")
+                        .cloned()
+                        .unwrap_or(Value::Bool(true)),
+                );
+
+                if location == "path"
+                    || parameter.get("required") == Some(&Value::Bool(true))
+                {
+                    required.push(Value::from(*key));
+                }
+            }
+
+            if properties.is_empty() {
+                continue;
+            }
+
+            // the query can be left out, if every
+            // parameter is optional
+            let default = if required.is_empty() { " = {}" } else { "" };
+            let declaration = declaration_name(suffix)?;
+            declarations.push((
+                declaration.clone(),
+                json!({ "type": "object", "required": required, "properties": properties }),
+            ));
+            arguments.push(format!(
+                "{location}: {}{default}",
+                self.0.backend.input_type(&declaration)?
+            ));
+        }
+
+        let body = operation
+            .get("requestBody")
+            .map(|body| resolve(document, body))
+            .and_then(|body| json_content(body).map(|schema| (body, schema)));
+        let mut request_body = String::new();
+        if let Some((body, schema)) = body {
+            let declaration = declaration_name("Body")?;
+            declarations.push((declaration.clone(), schema.clone()));
+
+            let optional = if body.get("required") == Some(&Value::Bool(true)) {
+                ""
+            } else {
+                "?"
+            };
+            // with codecs, the body is encoded into its
+            // json by the schema
+            let codecs = self.0.config.codecs
+                || ["date", "date-time", "partial-date-time"]
+                    .iter()
+                    .any(|format| {
+                        matches!(self.0.date_format_of(format), Some(DateFormat::Codec))
+                    });
+            let encoded = self
+                .0
+                .backend
+                .encode_value(&declaration, "body")
+                .filter(|_| codecs);
+            let body_type = match encoded {
+                Some(_) => self.0.backend.output_type(&declaration)?,
+                None => self.0.backend.input_type(&declaration)?,
+            };
+            arguments.push(format!("body{optional}: {body_type}"));
+            request_body = match (encoded, optional) {
+                (Some(encoded), "?") => format!(
+                    ", body: body === undefined ? undefined : JSON.stringify({encoded})"
+                ),
+                (Some(encoded), _) => format!(", body: JSON.stringify({encoded})"),
+                (None, _) => String::from(", body: JSON.stringify(body)"),
+            };
+        }
+
+        // the first successful response
+        let response = operation
+            .get("responses")
+            .and_then(|responses| responses.as_object())
+            .and_then(|responses| {
+                responses
+                    .iter()
+                    .filter(|(status, _)| status.starts_with('2'))
+                    .min_by_key(|(status, _)| status.as_str())
+            })
+            .and_then(|(_, response)| json_content(resolve(document, response)));
+        let result = match response {
+            Some(schema) => {
+                let declaration = declaration_name("Response")?;
+                declarations.push((declaration.clone(), schema.clone()));
+
+                format!(
+                    "return {};",
+                    self.0
+                        .backend
+                        .parse_value(&declaration, "await response.json()")?
+                )
+            },
+            None => String::from("return;"),
+        };
+
+        let mut url = String::new();
+        for (i, segment) in path.split(['{', '}']).enumerate() {
+            if i % 2 == 1 {
+                url.push_str(&format!(
+                    "${{encodeURIComponent(String(path[{}]))}}",
+                    serde_json::to_string(segment)?
+                ));
+            } else {
+                url.push_str(&segment.replace('`', "\\`").replace("${", "\\${"));
+            }
+        }
+        if arguments
+            .iter()
+            .any(|argument| argument.starts_with("query:"))
+        {
+            url.push_str("${searchParams(query)}");
+        }
+
+        let headers = if body.is_some() {
+            r#"{ ...clientConfig.init.headers, ...init?.headers, "Content-Type": "application/json" }"#
+        } else {
+            "{ ...clientConfig.init.headers, ...init?.headers }"
+        };
+        arguments.push(String::from("init?: RequestInit"));
+
+        let mut parsed = Vec::with_capacity(declarations.len() + 1);
+        for (declaration, schema) in declarations {
+            let schema: Schema =
+                serde_json::from_value(self.0.normalize_openapi(&schema)?)?;
+            parsed.push(
+                self.0
+                    .parse_declaration(&declaration, &schema, true, module.scope())?
+                    .to_string(),
+            );
+        }
+
+        parsed.push(format!(
+            "{}export async function {name}({}) {{\n  const response = await \
+             fetch(`${{clientConfig.baseUrl}}{url}`, {{ ...clientConfig.init, ...init, \
+             method: {}, headers: {headers}{request_body} }});\n  if (!response.ok) \
+             throw new Error(`${{response.status}} ${{response.statusText}}`);\n  \
+             {result}\n}}",
+            crate::backends::js_doc(
+                operation
+                    .get("summary")
+                    .or_else(|| operation.get("description"))
+                    .and_then(|description| description.as_str())
+            ),
+            arguments.join(", "),
+            serde_json::to_string(&method.to_ascii_uppercase())?,
+        ));
+
+        Ok(parsed.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::{
+        DateFormat,
+        Error,
+        Parser,
+        naming::{Case, Naming},
+    };
+
+    fn document() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "servers": [{ "url": "https://example.com/api" }],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "parameters": [{
+                            "name": "limit",
+                            "in": "query",
+                            "schema": { "type": "integer", "minimum": 0 },
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "The pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/components/schemas/Pet" },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "/pets/{petId}": {
+                    "parameters": [{
+                        "name": "petId",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string" },
+                    }],
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [{
+                            "name": "fields",
+                            "in": "query",
+                            "required": true,
+                            "schema": { "type": "array", "items": { "type": "string" } },
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "The pet",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Pet" },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                    "put": {
+                        "summary": "Replace a pet",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" },
+                                },
+                            },
+                        },
+                        "responses": { "204": { "description": "Replaced" } },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name", "born"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "born": { "type": "string", "format": "date-time" },
+                        },
+                    },
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn test_openapi_client() {
+        let parser = Parser::new(crate::Config {
+            date_format: DateFormat::Codec,
+            ..Default::default()
+        });
+        let result = parser.parse_openapi_client(&document()).unwrap();
+
+        // std::fs::write("tests/client.ts", &result).expect("Could not save
+        // result");
+        assert_eq!(include_str!("../tests/client.ts"), &result);
+        crate::parsers::check_types("client", &result);
+    }
+
+    #[test]
+    fn test_openapi_client_collision() {
+        let parser = Parser::default();
+
+        for component in ["GetPetResponse", "getPet", "clientConfig", "searchParams"] {
+            let mut document = document();
+            document["components"]["schemas"][component] = json!({ "type": "string" });
+
+            assert!(matches!(
+                parser.parse_openapi_client(&document),
+                Err(Error::SchemaError(_))
+            ));
+        }
+
+        // two operations with the same name
+        let mut document = document();
+        document["paths"]["/pets"]["get"]["operationId"] = json!("getPet");
+        assert!(matches!(
+            parser.parse_openapi_client(&document),
+            Err(Error::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn test_openapi_client_naming() {
+        let mut document = document();
+        document["paths"]["/pets/{petId}"]["get"]["responses"]["200"]["content"]
+            ["application/json"]["schema"] =
+            json!({ "$ref": "#/components/schemas/pet" });
+        document["components"]["schemas"]["pet"] = json!({ "type": "string" });
+
+        let parser = Parser::default()
+            .with_naming(Naming::default().case(Case::Pascal).prefix("z"));
+        let result = parser.parse_openapi_client(&document).unwrap();
+
+        assert!(result.contains("export const zPet2 = z.string();"));
+        assert!(result.contains("export const zGetPetResponse = zPet2;"));
+        assert!(result.contains("export const zListPetsResponse = z.array(zPet);"));
+        assert!(result.contains("return zGetPetResponse.parse(await response.json());"));
+        crate::parsers::check_types("client-naming", &result);
+    }
+}
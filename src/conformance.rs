@@ -0,0 +1,208 @@
+//! Check that serde and the generated schema
+//! accept the same json
+//!
+//! Requires node with the library of the target
+//! installed, like the tests of this crate.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use arbitrary::{Arbitrary, Unstructured};
+use schemars::{
+    JsonSchema,
+    r#gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::Parser;
+
+/// The bytes given to `Arbitrary` for one value
+const BYTES_PER_VALUE: usize = 256;
+
+/// Pseudo random bytes for `Arbitrary`, so a failed
+/// check can be repeated
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+
+    (0..len)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+/// Invalid versions of a value: every value with
+/// another type, and every object without one of
+/// its keys
+fn mutations(value: &Value) -> Vec<Value> {
+    let mut mutated = vec![match value {
+        Value::Null => Value::Bool(false),
+        Value::Bool(_) => Value::from("true"),
+        Value::Number(_) => Value::from("0"),
+        Value::String(_) => Value::from(0),
+        Value::Array(_) => Value::Object(Map::new()),
+        Value::Object(_) => Value::Array(Vec::new()),
+    }];
+
+    match value {
+        Value::Array(items) =>
+            for (i, item) in items.iter().enumerate() {
+                for item_mutated in mutations(item) {
+                    let mut items = items.clone();
+                    items[i] = item_mutated;
+                    mutated.push(Value::Array(items));
+                }
+            },
+        Value::Object(object) =>
+            for (key, property) in object {
+                let mut removed = object.clone();
+                removed.remove(key);
+                mutated.push(Value::Object(removed));
+
+                for property_mutated in mutations(property) {
+                    let mut object = object.clone();
+                    object.insert(key.clone(), property_mutated);
+                    mutated.push(Value::Object(object));
+                }
+            },
+        _ => {},
+    }
+
+    mutated
+}
+
+/// Generate `samples` values of `T`, and check that
+/// the schema of `T` accepts their json like serde
+/// does. Invalid versions of the json are checked
+/// as well, the schema has to reject the ones serde
+/// rejects and accept the others.
+///
+/// Panics with the json on a mismatch, like an
+/// `assert!`.
+///
+/// ```ignore
+/// #[derive(JsonSchema, Serialize, Deserialize, Arbitrary)]
+/// struct User { name: String, age: u8 }
+///
+/// #[test]
+/// fn user_conforms() {
+///     assert_conformance::<User>(&Parser::default(), 100);
+/// }
+/// ```
+pub fn assert_conformance<T>(parser: &Parser, samples: usize)
+where T: JsonSchema + Serialize + DeserializeOwned + for<'a> Arbitrary<'a> {
+    let mut settings = SchemaSettings::draft07();
+    settings.inline_subschemas = true;
+    let schema = SchemaGenerator::new(settings).into_root_schema_for::<T>();
+
+    let parsed = parser
+        .parse(&Schema::Object(schema.schema))
+        .expect("Could not parse the schema");
+    let backend = &parser.0.backend;
+    let import = backend
+        .import()
+        .expect("The target has no runtime to check the json with");
+
+    // the json, and if serde accepts it
+    let mut cases = Vec::new();
+
+    for seed in 0..samples as u64 {
+        let bytes = random_bytes(seed, BYTES_PER_VALUE);
+        let Ok(value) = T::arbitrary(&mut Unstructured::new(&bytes)) else {
+            continue;
+        };
+        let json = serde_json::to_value(&value).expect("Could not serialize a value");
+
+        // serde may not accept its own json, e.g. a
+        // float which isn't finite is `null`
+        for json in mutations(&json).into_iter().chain([json]) {
+            let accepted = serde_json::from_value::<T>(json.clone()).is_ok();
+            cases.push((json, accepted));
+        }
+    }
+
+    // the cases are read from stdin, as they are too
+    // long for an argument
+    let script = format!(
+        "{} const schema = {parsed}; let input = \"\"; process.stdin.on(\"data\", data \
+         => input += data); process.stdin.on(\"end\", () => \
+         console.log(JSON.stringify(JSON.parse(input).map(c => {{ try {{ {}; return \
+         true; }} catch {{ return false; }} }}))));",
+        parser.0.output.library_import(import, false),
+        backend
+            .parse_value("schema", "c")
+            .expect("Could not parse the json with the schema"),
+    );
+    let cases_json =
+        serde_json::to_vec(&cases.iter().map(|(json, _)| json).collect::<Vec<_>>())
+            .expect("Could not serialize the cases");
+
+    let mut node = Command::new("node")
+        .args(["-e", &script])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute process");
+    node.stdin
+        .take()
+        .expect("Could not open the stdin of node")
+        .write_all(&cases_json)
+        .expect("Could not write the cases");
+    let output = node.wait_with_output().expect("failed to execute process");
+
+    assert!(
+        output.status.success(),
+        "Failed to run the schema in node:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let results: Vec<bool> = serde_json::from_slice(&output.stdout)
+        .expect("Could not read the results of node");
+
+    for ((json, accepted), success) in cases.iter().zip(results) {
+        assert_eq!(
+            *accepted,
+            success,
+            "serde {} the json, but the schema {} it:\n{json}\nschema: {parsed}",
+            if *accepted { "accepts" } else { "rejects" },
+            if success { "accepts" } else { "rejects" },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Arbitrary;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    use super::assert_conformance;
+    use crate::Parser;
+
+    #[derive(JsonSchema, Serialize, Deserialize, Arbitrary)]
+    #[serde(tag = "kind", rename_all = "camelCase")]
+    enum TestKind {
+        Admin,
+        User { level: u8 },
+    }
+
+    #[derive(JsonSchema, Serialize, Deserialize, Arbitrary)]
+    struct TestSchema {
+        name: String,
+        age: Option<u16>,
+        tags: Vec<bool>,
+        kind: TestKind,
+    }
+
+    #[test]
+    fn test_conformance() { assert_conformance::<TestSchema>(&Parser::default(), 20); }
+}
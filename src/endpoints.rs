@@ -0,0 +1,423 @@
+//! Endpoints described in rust, for services
+//! without an OpenAPI document
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+
+use schemars::{
+    JsonSchema,
+    r#gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema,
+};
+
+use crate::{Error, Parser, ParserResult, Target, module::identifier};
+
+/// The http method of an endpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `HEAD`
+    Head,
+    /// `POST`
+    Post,
+    /// `PUT`
+    Put,
+    /// `PATCH`
+    Patch,
+    /// `DELETE`
+    Delete,
+    /// `OPTIONS`
+    Options,
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+            Method::Options => "OPTIONS",
+        })
+    }
+}
+
+/// Generates the schema of a type, and adds the
+/// types it references to the generator
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// An endpoint, e.g. of an axum handler
+///
+/// ```
+/// # use schemars::JsonSchema;
+/// # use schemars_to_zod::endpoints::{Endpoint, Method};
+/// # #[derive(JsonSchema)]
+/// # struct Pet { name: String }
+/// let endpoint = Endpoint::new("createPet", Method::Post, "/pets")
+///     .request::<Pet>()
+///     .response::<Pet>();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Endpoint {
+    name: String,
+    method: Method,
+    path: String,
+    request: Option<SchemaFn>,
+    response: Option<SchemaFn>,
+    query: Option<SchemaFn>,
+}
+
+impl Endpoint {
+    /// An endpoint without a request, response or
+    /// query
+    pub fn new(name: impl Into<String>, method: Method, path: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            method,
+            path: path.into(),
+            request: None,
+            response: None,
+            query: None,
+        }
+    }
+
+    /// The json body of the request
+    pub fn request<T: JsonSchema>(mut self) -> Self {
+        self.request = Some(SchemaGenerator::subschema_for::<T>);
+        self
+    }
+
+    /// The json body of the response
+    pub fn response<T: JsonSchema>(mut self) -> Self {
+        self.response = Some(SchemaGenerator::subschema_for::<T>);
+        self
+    }
+
+    /// The query parameters
+    pub fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(SchemaGenerator::subschema_for::<T>);
+        self
+    }
+}
+
+impl Parser {
+    /// Parse endpoints into a module with the
+    /// schemas of their types, and an `endpoints`
+    /// map, e.g. `{ "<name>": { method, path,
+    /// request, response, query } }`
+    ///
+    /// The schemas are generated with `settings`, e.g.
+    /// `SchemaSettings::openapi3()` for the same
+    /// schemas as an OpenAPI document. For
+    /// TypeScript, the map is the type `Endpoints`.
+    ///
+    /// ```
+    /// # use schemars::r#gen::SchemaSettings;
+    /// # use schemars_to_zod::{Parser, endpoints::{Endpoint, Method}};
+    /// let endpoints = [Endpoint::new("listPets", Method::Get, "/pets")];
+    /// let module = Parser::default()
+    ///     .parse_endpoints(&endpoints, SchemaSettings::draft07())
+    ///     .unwrap();
+    /// # let _ = module;
+    /// ```
+    pub fn parse_endpoints(
+        &self,
+        endpoints: &[Endpoint],
+        settings: SchemaSettings,
+    ) -> ParserResult {
+        self.0.output.require_typescript(
+            "Endpoints: endpoints can only be outputted as a .ts module",
+        )?;
+        let mut generator = SchemaGenerator::new(settings);
+        let mut schemas = Vec::with_capacity(endpoints.len());
+        let mut type_names = BTreeSet::new();
+
+        for endpoint in endpoints {
+            let mut type_name = identifier(&endpoint.name);
+            if let Some(first) = type_name.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+
+            if !type_names.insert(type_name.clone()) {
+                return Err(Error::SchemaError(
+                    "Endpoints: two endpoints have the same name",
+                ));
+            }
+
+            for (key, suffix, schema_fn) in [
+                ("request", "Request", endpoint.request),
+                ("response", "Response", endpoint.response),
+                ("query", "Query", endpoint.query),
+            ] {
+                if let Some(schema_fn) = schema_fn {
+                    schemas.push((
+                        &endpoint.name,
+                        key,
+                        self.0.naming.name(&format!("{type_name}{suffix}")),
+                        schema_fn(&mut generator),
+                    ));
+                }
+            }
+        }
+
+        let mut definitions = BTreeMap::new();
+        for (name, schema) in generator.take_definitions() {
+            definitions.insert(name, serde_json::to_value(schema)?);
+        }
+
+        let mut names: BTreeSet<_> = self
+            .0
+            .naming
+            .names(definitions.keys(), &[])
+            .into_values()
+            .collect();
+        if !schemas
+            .iter()
+            .all(|(_, _, declaration, _)| names.insert(declaration.clone()))
+        {
+            return Err(Error::SchemaError(
+                "Endpoints: a schema of an endpoint has the name of another declaration",
+            ));
+        }
+
+        let mut entries = BTreeMap::<&str, Vec<String>>::new();
+
+        for endpoint in endpoints {
+            entries.insert(&endpoint.name, vec![
+                format!(
+                    "method: {}",
+                    serde_json::to_string(&endpoint.method.to_string())?
+                ),
+                format!("path: {}", serde_json::to_string(&endpoint.path)?),
+            ]);
+        }
+
+        let mut module = self.parse_module(&definitions, |output, module| {
+            for (name, key, declaration, schema) in schemas {
+                output.push('\n');
+                self.0
+                    .parse_declaration(&declaration, &schema, true, module.scope())?
+                    .write_to(output)?;
+                output.push('\n');
+
+                if let Some(entry) = entries.get_mut(name.as_str()) {
+                    entry.push(format!("{key}: {declaration}"));
+                }
+            }
+
+            Ok(())
+        })?;
+
+        let is_type = matches!(self.0.config.target, Target::TypeScript);
+        let mut map = Vec::with_capacity(entries.len());
+
+        for (name, entry) in entries {
+            map.push(format!(
+                "{}: {{ {} }}",
+                serde_json::to_string(name)?,
+                entry.join(if is_type { "; " } else { ", " })
+            ));
+        }
+
+        module.push('\n');
+        module.push_str(&if is_type {
+            format!("export type Endpoints = {{ {} }};\n", map.join("; "))
+        } else {
+            format!(
+                "export const endpoints = {{ {} }} as const;\n",
+                map.join(", ")
+            )
+        });
+
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::{JsonSchema, r#gen::SchemaSettings};
+
+    use super::{Endpoint, Method};
+    use crate::{
+        Config,
+        Error,
+        Parser,
+        Target,
+        naming::{Case, Naming},
+    };
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Pet {
+        name: String,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct PetQuery {
+        limit: Option<u32>,
+    }
+
+    fn endpoints() -> Vec<Endpoint> {
+        vec![
+            Endpoint::new("listPets", Method::Get, "/pets")
+                .query::<PetQuery>()
+                .response::<Vec<Pet>>(),
+            Endpoint::new("createPet", Method::Post, "/pets")
+                .request::<Pet>()
+                .response::<Pet>(),
+        ]
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let parser = Parser::default();
+        let result = parser
+            .parse_endpoints(&endpoints(), SchemaSettings::draft07())
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const Pet = z.object({ "name": z.string() });"#,
+                "\n\n",
+                r#"export const PetQuery = z.object({ "limit": "#,
+                r#"z.nullish(z.uint32()) });"#,
+                "\n\n",
+                "export const ListPetsResponse = z.array(Pet);\n\n",
+                "export const ListPetsQuery = PetQuery;\n\n",
+                "export const CreatePetRequest = Pet;\n\n",
+                "export const CreatePetResponse = Pet;\n\n",
+                r#"export const endpoints = { "createPet": { method: "POST", "#,
+                r#"path: "/pets", request: CreatePetRequest, "#,
+                r#"response: CreatePetResponse }, "listPets": { method: "GET", "#,
+                r#"path: "/pets", response: ListPetsResponse, "#,
+                r#"query: ListPetsQuery } } as const;"#,
+                "\n",
+            ),
+            &result
+        );
+        crate::parsers::check_types("endpoints", &result);
+    }
+
+    #[test]
+    fn test_endpoints_names() {
+        let parser = Parser::default();
+
+        let duplicate = [
+            Endpoint::new("getPet", Method::Get, "/pets/{id}"),
+            Endpoint::new("getPet", Method::Delete, "/pets/{id}"),
+        ];
+        assert!(matches!(
+            parser.parse_endpoints(&duplicate, SchemaSettings::draft07()),
+            Err(Error::SchemaError(_))
+        ));
+
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct PetRequest {
+            name: String,
+        }
+
+        let collision =
+            [Endpoint::new("pet", Method::Post, "/pets").request::<PetRequest>()];
+        assert!(matches!(
+            parser.parse_endpoints(&collision, SchemaSettings::draft07()),
+            Err(Error::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn test_endpoints_naming() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        #[schemars(rename = "pet")]
+        struct NewPet {
+            name: String,
+        }
+
+        let parser = Parser::default()
+            .with_naming(Naming::default().case(Case::Pascal).suffix("Schema"));
+        let result = parser
+            .parse_endpoints(
+                &[
+                    Endpoint::new("getPet", Method::Get, "/pets/{id}").response::<Pet>(),
+                    Endpoint::new("createPet", Method::Post, "/pets").request::<NewPet>(),
+                ],
+                SchemaSettings::draft07(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const PetSchema = z.object({ "name": z.string() });"#,
+                "\n\n",
+                r#"export const PetSchema2 = z.object({ "name": z.string() });"#,
+                "\n\n",
+                "export const GetPetResponseSchema = PetSchema;\n\n",
+                "export const CreatePetRequestSchema = PetSchema2;\n\n",
+                r#"export const endpoints = { "createPet": { method: "POST", "#,
+                r#"path: "/pets", request: CreatePetRequestSchema }, "#,
+                r#""getPet": { method: "GET", path: "/pets/{id}", "#,
+                r#"response: GetPetResponseSchema } } as const;"#,
+                "\n",
+            ),
+            &result
+        );
+    }
+
+    #[test]
+    fn test_endpoints_typescript() {
+        let parser = Parser::new(Config {
+            target: Target::TypeScript,
+            ..Default::default()
+        });
+        let result = parser
+            .parse_endpoints(&endpoints(), SchemaSettings::draft07())
+            .unwrap();
+
+        assert!(result.ends_with(concat!(
+            r#"export type Endpoints = { "createPet": { method: "POST"; "#,
+            r#"path: "/pets"; request: CreatePetRequest; "#,
+            r#"response: CreatePetResponse }; "listPets": { method: "GET"; "#,
+            r#"path: "/pets"; response: ListPetsResponse; "#,
+            r#"query: ListPetsQuery } };"#,
+            "\n",
+        )));
+        crate::parsers::check_types("endpoints-typescript", &result);
+    }
+
+    #[test]
+    fn test_endpoints_openapi3() {
+        let parser = Parser::default();
+        let result = parser
+            .parse_endpoints(
+                &[Endpoint::new("findPet", Method::Get, "/pets")
+                    .response::<Option<Pet>>()],
+                SchemaSettings::openapi3(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const Pet = z.object({ "name": z.string() });"#,
+                "\n\n",
+                "export const FindPetResponse = z.nullable(Pet);\n\n",
+                r#"export const endpoints = { "findPet": { method: "GET", "#,
+                r#"path: "/pets", response: FindPetResponse } } as const;"#,
+                "\n",
+            ),
+            &result
+        );
+    }
+}
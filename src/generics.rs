@@ -0,0 +1,432 @@
+//! Generic factories for the instantiations of a
+//! generic type, e.g. `Page_for_User` and
+//! `Page_for_Order` become `Page(User)` and
+//! `Page(Order)`
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+
+use crate::{backends::Parameter, parsers::unescape_pointer};
+
+/// The separator of the name of a generic type and
+/// its arguments, used by schemars
+const FOR: &str = "_for_";
+
+/// The identifier of the type of a parameter, e.g.
+/// `T` or `U`
+fn candidate(index: usize) -> String {
+    match ["T", "U", "V", "W"].get(index) {
+        Some(parameter) => String::from(*parameter),
+        None => format!("T{index}"),
+    }
+}
+
+/// The `count` parameters of a factory, whose
+/// identifiers aren't `taken` in its module, e.g.
+/// the type `T` and the value `t`
+pub(crate) fn parameters(count: usize, taken: impl Fn(&str) -> bool) -> Vec<Parameter> {
+    let types = (0..).map(candidate).filter(|identifier| !taken(identifier));
+    let values = (0..)
+        .map(|index| candidate(index).to_lowercase())
+        .filter(|identifier| !taken(identifier));
+
+    types
+        .zip(values)
+        .take(count)
+        .map(|(type_name, value)| Parameter { type_name, value })
+        .collect()
+}
+
+/// The name a template references the parameter
+/// `index` with
+pub(crate) fn parameter_name(index: usize) -> String { format!("<{index}>") }
+
+/// The index of the parameter a template
+/// references with `name`
+pub(crate) fn parameter_index(name: &str) -> Option<usize> {
+    name.strip_prefix('<')?.strip_suffix('>')?.parse().ok()
+}
+
+/// What a value of a schema is, to know where a
+/// parameter can be
+#[derive(Clone, Copy)]
+enum Kind {
+    /// A schema
+    Schema,
+    /// An array of schemas, e.g. `anyOf`
+    Schemas,
+    /// A map of schemas, e.g. `properties`
+    SchemaMap,
+    /// Anything else, e.g. `required` or `enum`
+    Value,
+}
+
+impl Kind {
+    /// The kind of the value of `key` in an object
+    /// of this kind
+    fn child(self, key: &str) -> Option<Kind> {
+        Some(match self {
+            Kind::Schema => match key {
+                "additionalProperties"
+                | "additionalItems"
+                | "items"
+                | "not"
+                | "if"
+                | "then"
+                | "else"
+                | "contains"
+                | "propertyNames" => Kind::Schema,
+                "allOf" | "anyOf" | "oneOf" | "prefixItems" => Kind::Schemas,
+                "properties" | "patternProperties" | "definitions" | "$defs" =>
+                    Kind::SchemaMap,
+                _ => Kind::Value,
+            },
+            Kind::SchemaMap => Kind::Schema,
+            Kind::Schemas => return None,
+            Kind::Value => Kind::Value,
+        })
+    }
+
+    /// The kind of the items of an array of this
+    /// kind
+    fn item(self) -> Kind {
+        match self {
+            Kind::Schema | Kind::Schemas => Kind::Schema,
+            Kind::SchemaMap | Kind::Value => Kind::Value,
+        }
+    }
+}
+
+/// Find the template of values which only differ in
+/// their subschemas, the differing subschemas are
+/// the parameters
+#[derive(Default)]
+struct Template<'a> {
+    /// The subschemas of each value, per parameter
+    parameters: Vec<Vec<&'a Value>>,
+}
+
+impl<'a> Template<'a> {
+    fn unify(&mut self, values: &[&'a Value], kind: Kind) -> Option<Value> {
+        let first = values[0];
+        if values.iter().all(|value| *value == first) {
+            return Some(first.clone());
+        }
+
+        let parameters = self.parameters.len();
+        // a reference is replaced as a whole
+        let is_reference = values.iter().any(|value| value.get("$ref").is_some());
+        if !(matches!(kind, Kind::Schema) && is_reference)
+            && let Some(template) = self.unify_children(values, kind)
+        {
+            return Some(template);
+        }
+        self.parameters.truncate(parameters);
+
+        if !matches!(kind, Kind::Schema) || first.is_array() {
+            return None;
+        }
+
+        let index = match self
+            .parameters
+            .iter()
+            .position(|parameter| parameter == values)
+        {
+            Some(index) => index,
+            None => {
+                self.parameters.push(values.to_vec());
+                self.parameters.len() - 1
+            },
+        };
+
+        Some(json!({ "$ref": format!("#/definitions/{}", parameter_name(index)) }))
+    }
+
+    fn unify_children(&mut self, values: &[&'a Value], kind: Kind) -> Option<Value> {
+        match values[0] {
+            Value::Array(first) => {
+                let arrays = values
+                    .iter()
+                    .map(|value| {
+                        value.as_array().filter(|array| array.len() == first.len())
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                (0..first.len())
+                    .map(|i| {
+                        let items: Vec<_> =
+                            arrays.iter().map(|array| &array[i]).collect();
+                        self.unify(&items, kind.item())
+                    })
+                    .collect::<Option<_>>()
+                    .map(Value::Array)
+            },
+            Value::Object(first) => {
+                let objects = values
+                    .iter()
+                    .map(|value| {
+                        value.as_object().filter(|object| {
+                            object.len() == first.len()
+                                && first.keys().all(|key| object.contains_key(key))
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                first
+                    .keys()
+                    .map(|key| {
+                        let children: Vec<_> =
+                            objects.iter().map(|object| &object[key]).collect();
+                        Some((key.clone(), self.unify(&children, kind.child(key)?)?))
+                    })
+                    .collect::<Option<Map<_, _>>>()
+                    .map(Value::Object)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Check if a value references the schema `name`
+fn references(value: &Value, name: &str) -> bool {
+    match value {
+        Value::Object(object) =>
+            object
+                .get("$ref")
+                .and_then(|reference| reference.as_str())
+                .and_then(|reference| reference.rsplit('/').next())
+                .is_some_and(|reference| unescape_pointer(reference) == name)
+                || object.values().any(|value| references(value, name)),
+        Value::Array(values) => values.iter().any(|value| references(value, name)),
+        _ => false,
+    }
+}
+
+/// The generic factories of a module
+#[derive(Default)]
+pub(crate) struct Generics {
+    /// The template and the number of parameters
+    /// of each factory
+    pub(crate) factories: BTreeMap<String, (Value, usize)>,
+    /// The factory and the arguments of each
+    /// instantiation
+    pub(crate) instances: BTreeMap<String, (String, Vec<Value>)>,
+}
+
+impl Generics {
+    /// Find the instantiations of the same generic
+    /// type, which only differ in their subschemas
+    pub(crate) fn new(schemas: &BTreeMap<String, Value>) -> Self {
+        let mut families: BTreeMap<&str, Vec<&String>> = BTreeMap::new();
+        for name in schemas.keys() {
+            if let Some((generic, _)) = name.split_once(FOR)
+                && !generic.is_empty()
+                && !schemas.contains_key(generic)
+            {
+                families.entry(generic).or_default().push(name);
+            }
+        }
+
+        let mut generics = Self::default();
+
+        for (generic, members) in families {
+            if members.len() < 2 {
+                continue;
+            }
+
+            let values: Vec<_> = members.iter().map(|name| &schemas[*name]).collect();
+            let mut template = Template::default();
+            let Some(unified) = template.unify(&values, Kind::Schema) else {
+                continue;
+            };
+
+            let parameters = template.parameters.len();
+            let is_parameter = unified
+                .get("$ref")
+                .is_some_and(|reference| reference.as_str() == Some("#/definitions/<0>"));
+            if parameters == 0 || is_parameter {
+                continue;
+            }
+
+            let instances: Vec<_> = members
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let arguments: Vec<_> = template
+                        .parameters
+                        .iter()
+                        .map(|parameter| parameter[i].clone())
+                        .collect();
+                    ((*name).clone(), (String::from(generic), arguments))
+                })
+                .collect();
+
+            // an instantiation can't be its own argument
+            if instances.iter().any(|(name, (_, arguments))| {
+                arguments.iter().any(|argument| references(argument, name))
+            }) {
+                continue;
+            }
+
+            generics
+                .factories
+                .insert(String::from(generic), (unified, parameters));
+            generics.instances.extend(instances);
+        }
+
+        generics
+    }
+
+    /// The schemas of the module with the factories,
+    /// where an instantiation references its factory
+    /// and arguments
+    pub(crate) fn module(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+    ) -> BTreeMap<String, Value> {
+        schemas
+            .iter()
+            .map(|(name, schema)| match self.instances.get(name) {
+                Some((factory, arguments)) => {
+                    let mut all_of =
+                        vec![json!({ "$ref": format!("#/definitions/{factory}") })];
+                    all_of.extend(arguments.iter().cloned());
+                    let mut instance = json!({ "allOf": all_of });
+                    if let Some(description) = schema.get("description") {
+                        instance["description"] = description.clone();
+                    }
+                    (name.clone(), instance)
+                },
+                None => (name.clone(), schema.clone()),
+            })
+            .chain(
+                self.factories
+                    .iter()
+                    .map(|(name, (template, _))| (name.clone(), template.clone())),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        Config,
+        Parser,
+        Target,
+        output::{Output, OutputMode},
+    };
+
+    #[test]
+    fn test_generic_factories() {
+        let page = |item: &str| {
+            json!({
+                "description": "A page of items",
+                "type": "object",
+                "required": ["items", "total"],
+                "properties": {
+                    "items": {
+                        "type": "array",
+                        "items": { "$ref": format!("#/components/schemas/{item}") },
+                    },
+                    "total": { "type": "integer" },
+                },
+            })
+        };
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Page_for_User": page("User"),
+                    "Page_for_Order": page("Order"),
+                    "Pair_for_User_and_User": {
+                        "type": "array",
+                        "items": [
+                            { "$ref": "#/components/schemas/User" },
+                            { "$ref": "#/components/schemas/User" },
+                        ],
+                    },
+                    "User": { "type": "string" },
+                    "Order": { "type": "boolean" },
+                    "T": { "type": "null" },
+                },
+            },
+        });
+
+        let config = Config {
+            generic_factories: true,
+            ..Default::default()
+        };
+        let result = Parser::new(config).parse_openapi(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                "export const Order = z.boolean();\n\n",
+                "/** A page of items */\n",
+                "export const Page = <U extends z.ZodMiniType<any>>(t: U) => ",
+                r#"z.object({ "items": z.array(t), "total": z.int32() });"#,
+                "\n\n",
+                "/** A page of items */\n",
+                "export const Page_for_Order = Page(Order);\n\n",
+                "export const User = z.string();\n\n",
+                "/** A page of items */\n",
+                "export const Page_for_User = Page(User);\n\n",
+                "export const Pair_for_User_and_User = z.tuple([User, User]);\n\n",
+                "export const T = z.null();\n",
+            ),
+            &result
+        );
+
+        // the factory of a .ts module has types
+        let result = Parser::new(config)
+            .with_output(Output::default().mode(OutputMode::EsmJs))
+            .parse_openapi(&document)
+            .unwrap();
+        crate::parsers::check_module(&result);
+
+        // the factory of a .d.ts file has the same
+        // parameters as its type
+        let result = Parser::new(config)
+            .with_output(Output::default().mode(OutputMode::EsmJs))
+            .parse_openapi_types(&document)
+            .unwrap();
+        assert!(
+            result.contains(concat!(
+                r#"export type Page<U> = { "items": Array<U>; "total": number; };"#,
+                "\n",
+                "export declare const Page: <U>(t: z.ZodMiniType<U>) => ",
+                "z.ZodMiniType<Page<U>>;\n",
+            )),
+            "{result}"
+        );
+        crate::parsers::check_types("generics-declarations", &result);
+
+        let config = Config {
+            target: Target::TypeScript,
+            ..config
+        };
+        let result = Parser::new(config).parse_openapi(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                "export type Order = boolean;\n\n",
+                "/** A page of items */\n",
+                r#"export type Page<U> = { "items": Array<U>; "total": number; };"#,
+                "\n\n",
+                "/** A page of items */\n",
+                "export type Page_for_Order = Page<Order>;\n\n",
+                "export type User = string;\n\n",
+                "/** A page of items */\n",
+                "export type Page_for_User = Page<User>;\n\n",
+                "export type Pair_for_User_and_User = [User, User];\n\n",
+                "export type T = null;\n",
+            ),
+            &result
+        );
+        crate::parsers::check_types("generics-typescript", &result);
+    }
+}
@@ -31,21 +31,68 @@
 
 #![warn(missing_docs)]
 
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    sync::Mutex,
+};
 
 use schemars::schema::Schema;
 
+pub mod backends;
+mod client;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod endpoints;
+mod generics;
+pub mod messages;
+mod module;
+pub mod naming;
+mod openapi;
+pub mod output;
 mod parsers;
+pub mod snapshot;
+#[cfg(feature = "validate")]
+mod validate;
 
 #[cfg(feature = "pretty")]
 pub(crate) use dprint_plugin_typescript::configuration::Configuration as PrettyConfig;
+#[cfg(feature = "inner")]
+pub use module::Scope;
+#[cfg(not(feature = "inner"))]
+pub(crate) use module::Scope;
+#[doc(hidden)]
+pub use schemars;
+#[cfg(feature = "validate")]
+pub use validate::validate_output;
 
 #[cfg(feature = "pretty")]
-use crate::pretty::{default_pretty_conf, format_js};
+use crate::pretty::default_pretty_conf;
+use crate::{
+    backends::{
+        ArkTypeBackend,
+        Backend,
+        EffectBackend,
+        Js,
+        TypeScriptBackend,
+        ValibotBackend,
+        ZodBackend,
+    },
+    naming::Naming,
+    output::Output,
+};
 
 /// The import used for zod
 pub const ZOD_IMPORT: &str = r#"import * as z from "zod/mini";"#;
 
+/// The import used for valibot
+pub const VALIBOT_IMPORT: &str = r#"import * as v from "valibot";"#;
+
+/// The import used for ArkType
+pub const ARKTYPE_IMPORT: &str = r#"import { type } from "arktype";"#;
+
+/// The import used for effect/Schema
+pub const EFFECT_IMPORT: &str = r#"import { Schema } from "effect";"#;
+
 #[derive(Debug)]
 /// The errors which my be returned from the
 /// `Parser::parse` functions
@@ -62,9 +109,14 @@ pub enum Error {
     ForgotCheck(&'static str),
     /// serde_json::to_string returned an error
     JsonError(serde_json::Error),
+    /// Writing into a `fmt::Write` went wrong
+    WriteError(std::fmt::Error),
     /// Formatting the code went wrong
     #[cfg(feature = "pretty")]
     PrettifyError,
+    /// The output threw when it was evaluated
+    #[cfg(feature = "validate")]
+    ValidationError(String),
 }
 
 impl Display for Error {
@@ -74,8 +126,11 @@ impl Display for Error {
             Error::Unimplemented(err) => write!(f, "Unimplemented: {err}"),
             Error::ForgotCheck(err) => write!(f, "Forgot a check: {err}"),
             Error::JsonError(err) => write!(f, "Serde error: {err}"),
+            Error::WriteError(err) => write!(f, "Write error: {err}"),
             #[cfg(feature = "pretty")]
             Error::PrettifyError => write!(f, "Error when prettifying"),
+            #[cfg(feature = "validate")]
+            Error::ValidationError(err) => write!(f, "Invalid output: {err}"),
         }
     }
 }
@@ -86,8 +141,44 @@ impl From<serde_json::Error> for Error {
     fn from(value: serde_json::Error) -> Self { Error::JsonError(value) }
 }
 
+impl From<std::fmt::Error> for Error {
+    fn from(value: std::fmt::Error) -> Self { Error::WriteError(value) }
+}
+
 pub(crate) type ParserResult = Result<String, Error>;
 
+pub(crate) type JsResult = Result<Js, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+/// A problem found while parsing, which didn't
+/// stop the schema from being outputted
+///
+/// Get them with `Parser::take_diagnostics`
+pub enum Diagnostic {
+    /// A string format, or content encoding, is not
+    /// known and was outputted as `z.string()`
+    UnknownFormat(String),
+    /// A default value, given as json, doesn't match
+    /// its schema and was left out
+    InvalidDefault(String),
+    /// A keyword of a json schema is not supported
+    /// and was left out
+    IgnoredKeyword(String),
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::UnknownFormat(format) =>
+                write!(f, "Unknown string format {format:?}, used a plain string"),
+            Diagnostic::InvalidDefault(default) =>
+                write!(f, "Invalid default value {default}, left it out"),
+            Diagnostic::IgnoredKeyword(keyword) =>
+                write!(f, "Unsupported keyword {keyword:?}, left it out"),
+        }
+    }
+}
+
 #[cfg(feature = "pretty")]
 pub mod pretty {
     //! Helper functions and dprint exports
@@ -319,21 +410,134 @@ pub enum DateFormat {
     /// `z.coerce.date()`
     #[default]
     CoerceDate,
-    /// `z.iso.datetime()`
+    /// `z.iso.datetime({ offset: true })`,
+    /// `z.iso.datetime({ local: true })` or
+    /// `z.iso.date()`
     IsoStringDate,
     /// `z.date().check(z.refine(date => date.toJSON()))`
     DateToJson,
+    /// `z.codec(z.iso.datetime(), z.date(), { decode, encode })`
+    ///
+    /// Decodes the string into a `Date` and encodes
+    /// it back into the same string format
+    Codec,
+}
+
+/// How each date format should be outputted,
+/// overriding `Config::date_format`
+///
+/// Example, which keeps a `NaiveDate` as a string
+/// while converting a `DateTime` to a `Date`:
+///
+/// ```
+/// use schemars_to_zod::{Config, DateFormat, DateFormats};
+///
+/// let config = Config {
+///     date_format: DateFormat::CoerceDate,
+///     date_formats: DateFormats {
+///         date: Some(DateFormat::IsoStringDate),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// # let _ = config;
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DateFormats {
+    /// The format `"date"`, e.g. `NaiveDate`
+    pub date: Option<DateFormat>,
+    /// The format `"date-time"`, e.g.
+    /// `DateTime<Utc>`
+    pub date_time: Option<DateFormat>,
+    /// The format `"partial-date-time"`, e.g.
+    /// `NaiveDateTime`
+    pub partial_date_time: Option<DateFormat>,
+}
+
+/// What the schemas should be outputted as
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Target {
+    /// zod/mini schemas
+    #[default]
+    Zod,
+    /// TypeScript types, without any runtime
+    ///
+    /// The types describe the input of the zod
+    /// schemas, e.g. a date is a `Date`, as all date
+    /// formats but `DateFormat::IsoStringDate` and
+    /// `DateFormat::Codec` output one.
+    TypeScript,
+    /// valibot schemas
+    ///
+    /// Valibot has no codecs, so codecs and
+    /// embedded json are only decoded.
+    Valibot,
+    /// ArkType types
+    ///
+    /// ArkType has no codecs, so codecs and
+    /// embedded json are only decoded. Defaults
+    /// are only applied to properties.
+    ArkType,
+    /// effect/Schema schemas
+    ///
+    /// Defaults are only applied to properties.
+    Effect,
+}
+
+/// The order of the properties of an object
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropertyOrder {
+    /// Sorted by their key
+    #[default]
+    Alphabetical,
+    /// The order of the schema, which is the order
+    /// of the rust fields with the `preserve_order`
+    /// feature
+    Source,
+    /// The required properties before the optional
+    /// ones, otherwise like `Source`
+    RequiredFirst,
 }
 
 /// Configuration for the parser
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Config {
+    /// What the schemas should be outputted as
+    pub target: Target,
     /// How a date should be outputted
     pub date_format: DateFormat,
+    /// How a date should be outputted per format,
+    /// falling back to `date_format`
+    pub date_formats: DateFormats,
     /// Don't add `z.optional(<schema>)` to object properties
     pub ignore_undefined: bool,
     /// Use `z.unknown()` instead of `z.any()`
     pub prefer_unknown: bool,
+    /// The order of the properties of an object
+    pub property_order: PropertyOrder,
+    /// Use `z.codec` for durations, base64 bytes and
+    /// 64-bit integer strings, so they decode into
+    /// `number`, `Uint8Array` and `bigint` and encode
+    /// back into their wire format
+    ///
+    /// Durations decode into whole milliseconds, up
+    /// to `Number.MAX_SAFE_INTEGER`.
+    ///
+    /// Dates are configured with
+    /// `DateFormat::Codec`
+    pub codecs: bool,
+    /// Use `z.prefault(<schema>, <default>)` instead
+    /// of `z._default(<schema>, <default>)`, so
+    /// defaults are parsed like any other input
+    ///
+    /// `z.prefault` is always used for schemas
+    /// which transform their input, e.g. into a
+    /// `Date`
+    pub prefault: bool,
+    /// Declare a generic factory for instantiations
+    /// of the same generic type in a module, e.g.
+    /// `Page_for_User = Page(User)`
+    pub generic_factories: bool,
 }
 
 /// The inside of the parser, which contains all
@@ -341,10 +545,72 @@ pub struct Config {
 #[cfg(feature = "inner")]
 pub struct ParserInner {
     config: Config,
+    backend: Box<dyn Backend>,
+    /// The problems found
+    diagnostics: Mutex<Vec<Diagnostic>>,
+    /// How the declarations of a module are named
+    naming: Naming,
+    /// How modules are outputted
+    output: Output,
 }
 #[cfg(not(feature = "inner"))]
 pub(crate) struct ParserInner {
     config: Config,
+    backend: Box<dyn Backend>,
+    /// The problems found
+    diagnostics: Mutex<Vec<Diagnostic>>,
+    /// How the declarations of a module are named
+    naming: Naming,
+    /// How modules are outputted
+    output: Output,
+}
+
+impl ParserInner {
+    /// Report a problem, which doesn't stop the
+    /// parsing
+    pub(crate) fn report(&self, diagnostic: Diagnostic, scope: Scope<'_>) {
+        if let Some(diagnostic) = scope.report(diagnostic) {
+            self.diagnostics
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .push(diagnostic);
+        }
+    }
+
+    /// Parse a schema into a named declaration, with
+    /// the description of the schema as JSDoc, which
+    /// is exported in place with `export`
+    pub(crate) fn parse_declaration(
+        &self,
+        name: &str,
+        schema: &Schema,
+        export: bool,
+        scope: Scope<'_>,
+    ) -> JsResult {
+        let description = match schema {
+            Schema::Object(object) => object
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.description.as_deref()),
+            Schema::Bool(_) => None,
+        };
+
+        self.backend.declaration(
+            name,
+            self.parse_schema(schema, scope)?,
+            description,
+            export,
+        )
+    }
+
+    /// The identifier of the declaration of the
+    /// schema `name`
+    pub(crate) fn declaration_name(&self, name: &str, scope: Scope<'_>) -> String {
+        scope
+            .name(name)
+            .map(String::from)
+            .unwrap_or_else(|| self.naming.name(name))
+    }
 }
 
 /// The main parser which can convert schemars's
@@ -384,43 +650,139 @@ pub struct Parser(
 
 impl Default for Parser {
     fn default() -> Self {
-        Self(ParserInner {
-            config: Config {
-                date_format: DateFormat::CoerceDate,
-                ignore_undefined: false,
-                prefer_unknown: true,
-            },
+        Self::new(Config {
+            target: Target::Zod,
+            date_format: DateFormat::CoerceDate,
+            date_formats: DateFormats::default(),
+            ignore_undefined: false,
+            prefer_unknown: true,
+            property_order: PropertyOrder::Alphabetical,
+            codecs: false,
+            prefault: false,
+            generic_factories: false,
         })
     }
 }
 
 impl Parser {
     /// Create a new parser
-    pub fn new(config: Config) -> Self { Parser(ParserInner { config }) }
+    pub fn new(config: Config) -> Self {
+        let backend: Box<dyn Backend> = match config.target {
+            Target::Zod => Box::new(ZodBackend),
+            Target::TypeScript => Box::new(TypeScriptBackend),
+            Target::Valibot => Box::new(ValibotBackend),
+            Target::ArkType => Box::new(ArkTypeBackend),
+            Target::Effect => Box::new(EffectBackend),
+        };
+
+        Self::with_backend(config, backend)
+    }
+
+    /// Create a new parser with a custom backend,
+    /// `Config::target` is ignored
+    pub fn with_backend(config: Config, backend: Box<dyn Backend>) -> Self {
+        Parser(ParserInner {
+            config,
+            backend,
+            diagnostics: Mutex::new(Vec::new()),
+            naming: Naming::default(),
+            output: Output::default(),
+        })
+    }
+
+    /// Name the declarations of modules, e.g. of
+    /// `parse_openapi`, with `naming`
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.0.naming = naming;
+        self
+    }
+
+    /// Output modules, e.g. of `parse_openapi`, as
+    /// described by `output`
+    pub fn with_output(mut self, output: Output) -> Self {
+        self.0.output = output;
+        self
+    }
 
     /// Parse a schema
-    pub fn parse(&self, schema: &Schema) -> ParserResult { self.0.parse_schema(schema) }
+    pub fn parse(&self, schema: &Schema) -> ParserResult {
+        Ok(self.0.parse_schema(schema, Scope::default())?.to_string())
+    }
+
+    /// Parse a schema into `writer`, e.g. to append
+    /// it to a buffer without another `String`
+    ///
+    /// Use `output::IoWriter` to write into a file.
+    pub fn parse_into(
+        &self,
+        schema: &Schema,
+        writer: &mut impl std::fmt::Write,
+    ) -> Result<(), Error> {
+        Ok(self
+            .0
+            .parse_schema(schema, Scope::default())?
+            .write_to(writer)?)
+    }
+
+    /// Parse a draft-07 or draft 2020-12 json
+    /// schema, which wasn't generated by schemars
+    ///
+    /// Only local references are supported, which
+    /// may not be recursive.
+    pub fn parse_json(&self, schema: &serde_json::Value) -> ParserResult {
+        let schema: Schema = serde_json::from_value(self.0.normalize_json(schema)?)?;
+
+        self.parse(&schema)
+    }
+
+    /// Parse a schema of schemars 1.x
+    #[cfg(feature = "schemars1")]
+    pub fn parse_schemars1(&self, schema: &schemars1::Schema) -> ParserResult {
+        self.parse_json(schema.as_value())
+    }
+
+    /// Parse a schema into a named declaration,
+    /// e.g. `export const <name> = <schema>;` or
+    /// `export type <name> = <type>;`, with the
+    /// description of the schema as JSDoc
+    pub fn parse_declaration(&self, name: &str, schema: &Schema) -> ParserResult {
+        Ok(self
+            .0
+            .parse_declaration(name, schema, true, Scope::default())?
+            .to_string())
+    }
+
+    /// Get the problems found while parsing, which
+    /// didn't stop the schema from being outputted,
+    /// and clear them
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        std::mem::take(
+            &mut *self
+                .0
+                .diagnostics
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()),
+        )
+    }
 
     /// Parse a schema and format it with the
     /// given config
     #[cfg(feature = "pretty")]
     pub fn parse_pretty(&self, schema: &Schema, config: &PrettyConfig) -> ParserResult {
-        let parsed = self.0.parse_schema(schema)?;
+        let parsed = self.0.parse_schema(schema, Scope::default())?;
 
-        format_js(&parsed, ".js", config)
-            .ok()
-            .ok_or(Error::PrettifyError)
+        self.0.backend.format(&parsed.to_string(), config)
     }
 
     /// Parse a schema and format it with the
     /// default config
     #[cfg(feature = "pretty")]
     pub fn parse_pretty_default(&self, schema: &Schema) -> ParserResult {
-        let parsed = self.0.parse_schema(schema)?;
+        let parsed = self.0.parse_schema(schema, Scope::default())?;
 
-        format_js(&parsed, ".js", &default_pretty_conf())
-            .ok()
-            .ok_or(Error::PrettifyError)
+        self.0
+            .backend
+            .format(&parsed.to_string(), &default_pretty_conf())
     }
 }
 
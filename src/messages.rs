@@ -0,0 +1,312 @@
+//! Tagged message enums, e.g. of a realtime
+//! protocol over a WebSocket
+
+use std::collections::BTreeMap;
+
+use schemars::{
+    JsonSchema,
+    r#gen::{SchemaGenerator, SchemaSettings},
+    schema::{Schema, SchemaObject},
+};
+use serde_json::Value;
+
+use crate::{
+    Error,
+    JsResult,
+    Parser,
+    ParserResult,
+    backends::Js,
+    module::{Module, identifier},
+};
+
+/// The error of a declaration of a message, which
+/// has the name of another declaration
+const COLLISION: &str =
+    "Messages: a declaration of a message has the name of another declaration";
+
+/// A tagged message enum, e.g. `ServerMessage`
+#[derive(Clone, Debug)]
+pub struct MessageEnum {
+    name: String,
+    schema: fn(&mut SchemaGenerator) -> Schema,
+}
+
+impl MessageEnum {
+    /// The message enum `T`, named like its schema
+    pub fn new<T: JsonSchema>() -> Self {
+        Self {
+            name: T::schema_name(),
+            schema: T::json_schema,
+        }
+    }
+
+    /// Use another name for the declarations
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+/// Turn a tag into a part of an identifier, e.g.
+/// `join_room` into `JoinRoom`
+fn pascal_case(tag: &str) -> String {
+    tag.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut part = part.to_owned();
+            part[..1].make_ascii_uppercase();
+            part
+        })
+        .collect()
+}
+
+/// The value of the tag of a variant
+fn tag_value<'a>(variant: &'a Schema, key: &str) -> Option<&'a Value> {
+    let Schema::Object(variant) = variant else {
+        return None;
+    };
+    let Some(Schema::Object(tag)) = variant.object.as_ref()?.properties.get(key) else {
+        return None;
+    };
+
+    match (&tag.const_value, tag.enum_values.as_deref()) {
+        (Some(value), _) | (None, Some([value])) => Some(value),
+        _ => None,
+    }
+}
+
+impl Parser {
+    /// Parse tagged message enums into a module with
+    /// a declaration for each variant, e.g.
+    /// `ServerMessageJoin`, the discriminated union
+    /// of the variants, and a dispatcher, e.g.
+    /// `parseServerMessage(raw)`
+    ///
+    /// The dispatcher parses the json of a message
+    /// with the schema of its variant. It is named
+    /// after the enum, without the `Naming` of the
+    /// declarations, as it isn't a schema.
+    pub fn parse_messages(&self, messages: &[MessageEnum]) -> ParserResult {
+        self.0.output.require_typescript(
+            "Messages: messages can only be outputted as a .ts module",
+        )?;
+        let mut generator = SchemaGenerator::new(SchemaSettings::draft07());
+        let schemas: Vec<_> = messages
+            .iter()
+            .map(|message| (message, (message.schema)(&mut generator)))
+            .collect();
+
+        let mut definitions = BTreeMap::new();
+        for (name, schema) in generator.take_definitions() {
+            definitions.insert(name, serde_json::to_value(schema)?);
+        }
+
+        self.parse_module(&definitions, |output, module| {
+            for (message, schema) in schemas {
+                output.push('\n');
+                self.parse_message(&message.name, &schema, module)?
+                    .write_to(output)?;
+                output.push('\n');
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Parse one message enum into its declarations
+    /// and dispatcher
+    fn parse_message(
+        &self,
+        name: &str,
+        schema: &Schema,
+        module: &mut Module,
+    ) -> JsResult {
+        let name = identifier(name);
+        let (object, variants) = match schema {
+            Schema::Object(
+                object @ SchemaObject {
+                    subschemas: Some(subschemas),
+                    ..
+                },
+            ) => match (&subschemas.one_of, &subschemas.any_of) {
+                (Some(variants), _) | (None, Some(variants)) => (object, variants),
+                _ =>
+                    return Err(Error::SchemaError("Messages: the enum has no variants")),
+            },
+            _ => return Err(Error::SchemaError("Messages: the enum has no variants")),
+        };
+
+        let Some(key) = self.0.has_discriminated(object, variants) else {
+            return Err(Error::SchemaError("Messages: the enum has to be tagged"));
+        };
+        let key_json = serde_json::to_string(&key)?;
+
+        let mut declarations = Vec::with_capacity(variants.len() + 2);
+        let mut names = Vec::with_capacity(variants.len());
+        let mut cases = Vec::with_capacity(variants.len());
+
+        for variant in variants {
+            let Some(tag) = tag_value(variant, &key) else {
+                return Err(Error::SchemaError("Messages: a variant has no tag"));
+            };
+            let variant_name = module.declare(
+                self.0.naming.name(&format!(
+                    "{name}{}",
+                    pascal_case(&match tag {
+                        Value::String(tag) => tag.clone(),
+                        tag => tag.to_string(),
+                    })
+                )),
+                COLLISION,
+            )?;
+
+            declarations.push(self.0.parse_declaration(
+                &variant_name,
+                variant,
+                true,
+                module.scope(),
+            )?);
+            cases.push(format!(
+                "    case {}: return {};",
+                serde_json::to_string(tag)?,
+                self.0.backend.parse_value(&variant_name, "message")?
+            ));
+            names.push(self.0.backend.reference(&variant_name)?);
+        }
+
+        let union_name = module.declare(self.0.naming.name(&name), COLLISION)?;
+        let dispatcher = module.declare(format!("parse{name}"), COLLISION)?;
+        declarations.push(
+            self.0.backend.declaration(
+                &union_name,
+                self.0.backend.discriminated_union(&key, names)?,
+                object
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.description.as_deref()),
+                true,
+            )?,
+        );
+        declarations.push(
+            format!(
+                "export function {dispatcher}(raw: string) {{\n  const message = \
+                 JSON.parse(raw);\n  switch (message?.[{key_json}]) {{\n{}\n    \
+                 default: throw new Error(`Unknown {name} \
+                 ${{JSON.stringify(message?.[{key_json}])}}`);\n  }}\n}}",
+                cases.join("\n")
+            )
+            .into(),
+        );
+
+        Ok(Js::join(declarations, "\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::JsonSchema;
+
+    use super::MessageEnum;
+    use crate::{Error, Parser, naming::Naming};
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Room {
+        id: u32,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    enum ServerMessage {
+        Joined { room: Room },
+        ChatMessage { text: String },
+    }
+
+    #[test]
+    fn test_messages() {
+        let parser = Parser::default();
+        let result = parser
+            .parse_messages(&[MessageEnum::new::<ServerMessage>()])
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const Room = z.object({ "id": z.uint32() });"#,
+                "\n\n",
+                r#"export const ServerMessageJoined = z.object({ "room": Room, "#,
+                r#""type": z.literal("joined") });"#,
+                "\n\n",
+                r#"export const ServerMessageChatMessage = z.object({ "text": z.string(), "#,
+                r#""type": z.literal("chat_message") });"#,
+                "\n\n",
+                r#"export const ServerMessage = z.discriminatedUnion("type", "#,
+                "[ServerMessageJoined, ServerMessageChatMessage]);\n\n",
+                "export function parseServerMessage(raw: string) {\n",
+                "  const message = JSON.parse(raw);\n",
+                "  switch (message?.[\"type\"]) {\n",
+                "    case \"joined\": return ServerMessageJoined.parse(message);\n",
+                "    case \"chat_message\": return \
+                 ServerMessageChatMessage.parse(message);\n",
+                "    default: throw new Error(`Unknown ServerMessage ",
+                "${JSON.stringify(message?.[\"type\"])}`);\n",
+                "  }\n}\n",
+            ),
+            &result
+        );
+        crate::parsers::check_types("messages", &result);
+    }
+
+    #[test]
+    fn test_messages_naming() {
+        let parser = Parser::default().with_naming(Naming::default().prefix("z"));
+        let result = parser
+            .parse_messages(&[MessageEnum::new::<ServerMessage>()])
+            .unwrap();
+
+        assert!(result.contains(concat!(
+            r#"export const zServerMessageJoined = z.object({ "room": zRoom, "#,
+            r#""type": z.literal("joined") });"#,
+        )));
+        assert!(result.contains(concat!(
+            r#"export const zServerMessage = z.discriminatedUnion("type", "#,
+            "[zServerMessageJoined, zServerMessageChatMessage]);\n\n",
+            "export function parseServerMessage(raw: string) {\n",
+        )));
+    }
+
+    #[test]
+    fn test_messages_collision() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        #[schemars(rename = "ClientMessageJoin")]
+        struct Join {
+            room: u32,
+        }
+
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        #[serde(tag = "type", rename_all = "snake_case")]
+        enum ClientMessage {
+            Join { join: Join },
+        }
+
+        let parser = Parser::default();
+
+        // a definition has the name of a variant
+        assert!(matches!(
+            parser.parse_messages(&[MessageEnum::new::<ClientMessage>()]),
+            Err(Error::SchemaError(_))
+        ));
+        // two enums have the same name
+        assert!(matches!(
+            parser.parse_messages(&[
+                MessageEnum::new::<ServerMessage>(),
+                MessageEnum::new::<ServerMessage>(),
+            ]),
+            Err(Error::SchemaError(_))
+        ));
+    }
+}
@@ -0,0 +1,1109 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    sync::Mutex,
+};
+
+use schemars::schema::Schema;
+use serde_json::Value;
+
+use crate::{
+    Diagnostic,
+    Error,
+    JsResult,
+    Parser,
+    ParserResult,
+    Target,
+    backends::{Backend, Js, Parameter, TypeScriptBackend, TypeScriptOutputBackend},
+    generics::{self, Generics},
+};
+
+/// The name of the module re-exporting all
+/// modules of `parse_module_files`
+const INDEX: &str = "index";
+
+/// The suffix of the type of the output of a
+/// schema in a `.d.ts` file, e.g. `UserOutput`
+const OUTPUT: &str = "Output";
+
+/// The parts of a module, which are written in
+/// order
+struct ModuleParts<'a> {
+    /// The imports
+    header: Option<String>,
+    /// The exports
+    footer: Option<String>,
+    generics: &'a Generics,
+    schemas: &'a BTreeMap<String, Value>,
+    /// The schemas declared, at their position in
+    /// the module
+    declarations: Vec<(usize, &'a String)>,
+}
+
+/// The declarations of the schemas of a `.d.ts`
+/// file, next to the types of their json
+#[derive(Clone, Copy)]
+struct Declared<'a> {
+    /// The backend of the schemas
+    backend: &'a dyn Backend,
+    /// The parser and the module of the types of
+    /// their output, which isn't their json with
+    /// codecs
+    outputs: Option<(&'a Parser, &'a Module)>,
+}
+
+/// The parsers of the `.d.ts` file of a module,
+/// and their modules
+struct Types {
+    /// Of the types of the json
+    json: (Parser, Module),
+    /// Of the types of the output, with codecs
+    outputs: Option<(Parser, Module)>,
+}
+
+impl Types {
+    fn name_module(&mut self, schemas: &BTreeMap<String, Value>) {
+        let (parser, module) = &mut self.json;
+        *module = parser.name_module(schemas, "");
+        if let Some((parser, module)) = &mut self.outputs {
+            *module = parser.name_module(schemas, OUTPUT);
+        }
+    }
+
+    /// The types declared for the schema `name`
+    fn declared(&self, name: &str) -> Vec<String> {
+        let (parser, module) = &self.json;
+        let mut declared = vec![parser.0.declaration_name(name, module.scope())];
+        declared.extend(
+            self.outputs
+                .as_ref()
+                .map(|(parser, module)| parser.0.declaration_name(name, module.scope())),
+        );
+
+        declared
+    }
+
+    /// Write the `.d.ts` file, which declares the
+    /// schemas of `backend`
+    fn write_module(
+        &self,
+        parts: ModuleParts<'_>,
+        backend: &dyn Backend,
+        writer: &mut impl Write,
+    ) -> Result<(), Error> {
+        let (parser, module) = &self.json;
+        let declared = Declared {
+            backend,
+            outputs: self
+                .outputs
+                .as_ref()
+                .map(|(parser, module)| (parser, module)),
+        };
+
+        parser.write_module(parts, module, Some(declared), writer, Ok)
+    }
+}
+
+/// The state of a module while it's parsed, which
+/// is passed down to its declarations in a `Scope`
+#[derive(Default)]
+pub(crate) struct Module {
+    /// The identifier of the declaration of each
+    /// schema
+    names: BTreeMap<String, String>,
+    /// The position of each declaration, references
+    /// to the ones not outputted yet are lazy
+    pending: BTreeMap<String, usize>,
+    /// The identifiers declared next to the schemas,
+    /// e.g. by a client
+    declared: BTreeSet<String>,
+    /// The problems found, with the position of the
+    /// declaration they are found in
+    diagnostics: Mutex<Vec<(usize, Diagnostic)>>,
+}
+
+impl Module {
+    /// The scope after every declaration of the
+    /// schemas, e.g. of the declarations added to
+    /// the module
+    pub(crate) fn scope(&self) -> Scope<'_> {
+        Scope {
+            module: Some(self),
+            position: usize::MAX,
+            parameters: &[],
+        }
+    }
+
+    /// Declare the identifier of a declaration next
+    /// to the schemas, failing with `err` if it is
+    /// already declared
+    pub(crate) fn declare(
+        &mut self,
+        identifier: String,
+        err: &'static str,
+    ) -> Result<String, Error> {
+        if self.names.values().any(|name| *name == identifier)
+            || !self.declared.insert(identifier.clone())
+        {
+            return Err(Error::SchemaError(err));
+        }
+
+        Ok(identifier)
+    }
+}
+
+/// Where a schema is parsed, in the declaration at
+/// a position of a module, or on its own
+#[derive(Clone, Copy, Default)]
+pub struct Scope<'a> {
+    module: Option<&'a Module>,
+    position: usize,
+    /// The identifiers a template of a factory
+    /// references its parameters with
+    parameters: &'a [String],
+}
+
+impl<'a> Scope<'a> {
+    /// Check if the declaration `name` of the module
+    /// is not outputted before this one
+    pub(crate) fn is_pending(self, name: &str) -> bool {
+        self.module
+            .and_then(|module| module.pending.get(name))
+            .is_some_and(|position| *position >= self.position)
+    }
+
+    /// The identifier of the declaration `name` of
+    /// the module
+    pub(crate) fn name(self, name: &str) -> Option<&'a str> {
+        if let Some(index) = generics::parameter_index(name) {
+            return self.parameters.get(index).map(String::as_str);
+        }
+
+        self.module?.names.get(name).map(String::as_str)
+    }
+
+    /// Check if the identifier is declared in the
+    /// module
+    pub(crate) fn is_taken(self, identifier: &str) -> bool {
+        self.module.is_some_and(|module| {
+            module.declared.contains(identifier)
+                || module.names.values().any(|name| name == identifier)
+        })
+    }
+
+    /// Report a problem to the module, or give it
+    /// back outside of a module
+    pub(crate) fn report(self, diagnostic: Diagnostic) -> Option<Diagnostic> {
+        let Some(module) = self.module else {
+            return Some(diagnostic);
+        };
+
+        module
+            .diagnostics
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push((self.position, diagnostic));
+        None
+    }
+}
+
+/// The words which can't be an identifier, and the
+/// primitive types of TypeScript
+const RESERVED_WORDS: [&str; 58] = [
+    "any",
+    "arguments",
+    "await",
+    "bigint",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "never",
+    "new",
+    "null",
+    "number",
+    "object",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "symbol",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "unknown",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Turn the name of a schema into a js identifier
+pub(crate) fn identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+
+    if RESERVED_WORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+
+    identifier
+}
+
+impl Parser {
+    /// Parse named json schemas, which are already
+    /// normalized, into a module with a declaration
+    /// for each schema, and add declarations to it
+    /// with `extend`
+    ///
+    /// Declarations are ordered so they come before
+    /// their references, cyclic references are lazy.
+    /// Inside `extend` the schemas are referenced
+    /// through the scope of the module, by its names,
+    /// e.g. with the number of a name which collides.
+    pub(crate) fn parse_module(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+        extend: impl FnOnce(&mut String, &mut Module) -> Result<(), Error>,
+    ) -> ParserResult {
+        let mut output = String::new();
+        let mut module = Module::default();
+        let result = self
+            .write_module_of(schemas, &mut module, &mut output, Ok)
+            .and_then(|()| {
+                // every declaration is outputted, so none is
+                // referenced lazily
+                module.pending.clear();
+                extend(&mut output, &mut module)
+            });
+
+        self.finish_module(module);
+        result.map(|()| output)
+    }
+
+    /// Parse named json schemas into a module, and
+    /// write each declaration into `writer`
+    ///
+    /// Every declaration, and the import, is passed
+    /// through `format`. With the `rayon` feature the
+    /// declarations are parsed and formatted in
+    /// parallel, and written in the same order.
+    pub(crate) fn parse_module_into(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+        writer: &mut impl Write,
+        format: impl Fn(Js) -> JsResult + Sync,
+    ) -> Result<(), Error> {
+        let mut module = Module::default();
+        let result = self.write_module_of(schemas, &mut module, writer, format);
+
+        self.finish_module(module);
+        result
+    }
+
+    /// Write the module of named json schemas into
+    /// `writer`, with the state of `module`
+    fn write_module_of(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+        module: &mut Module,
+        writer: &mut impl Write,
+        format: impl Fn(Js) -> JsResult + Sync,
+    ) -> Result<(), Error> {
+        self.check_output()?;
+        let (generics, with_generics) = self.module_generics(schemas);
+        let schemas = &*with_generics;
+        *module = self.name_module(schemas, "");
+
+        let order = self.module_order(schemas);
+        let declarations: Vec<_> = order.into_iter().enumerate().collect();
+        module.pending = declarations
+            .iter()
+            .map(|(position, name)| ((*name).clone(), *position))
+            .collect();
+
+        let parts = ModuleParts {
+            header: self
+                .0
+                .backend
+                .import()
+                .map(|import| self.0.output.library_import(import, false)),
+            footer: self.exports(module, &declarations),
+            generics: &generics,
+            schemas,
+            declarations,
+        };
+        self.write_module(parts, module, None, writer, format)
+    }
+
+    /// Parse named json schemas into the `.d.ts`
+    /// declaration file of their module, which
+    /// declares the type of the json of each schema
+    pub(crate) fn parse_module_types(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+        writer: &mut impl Write,
+    ) -> Result<(), Error> {
+        let mut types = self.types_parser();
+        let (generics, module) = self.module_generics(schemas);
+        let schemas = &*module;
+        types.name_module(schemas);
+
+        let parts = ModuleParts {
+            header: self
+                .0
+                .backend
+                .import()
+                .map(|import| self.0.output.library_import(import, true)),
+            footer: None,
+            generics: &generics,
+            schemas,
+            declarations: self.module_order(schemas).into_iter().enumerate().collect(),
+        };
+        types.write_module(parts, &*self.0.backend, writer)
+    }
+
+    /// Parse named json schemas into a module per
+    /// group, and an `index` module re-exporting all
+    /// of them
+    ///
+    /// A module imports the declarations it references
+    /// from the other modules. References between
+    /// modules which import each other are lazy, so
+    /// they are not used before they are declared.
+    /// Outside of TypeScript every module has a
+    /// `.d.ts` declaration file.
+    pub(crate) fn parse_module_files(
+        &self,
+        schemas: &BTreeMap<String, Value>,
+        group: impl Fn(&str) -> String,
+    ) -> Result<BTreeMap<String, String>, Error> {
+        self.check_output()?;
+        let (generics, module) = self.module_generics(schemas);
+        let schemas = &*module;
+        let groups: BTreeMap<&str, String> = schemas
+            .keys()
+            .map(|name| (name.as_str(), group(name)))
+            .collect();
+
+        if groups.values().any(|group| group == INDEX) {
+            return Err(Error::SchemaError(
+                "Files: no group can be named index, which re-exports all groups",
+            ));
+        }
+        // a group is the name of its file, and the
+        // binding of its module in CommonJS
+        if groups.values().any(|group| identifier(group) != *group) {
+            return Err(Error::SchemaError(
+                "Files: a group has to be a js identifier, e.g. users",
+            ));
+        }
+
+        // the schemas each group imports, per group
+        // they are declared in
+        let mut imports: BTreeMap<&str, BTreeMap<&str, BTreeSet<&str>>> = BTreeMap::new();
+        for (name, group) in &groups {
+            let imported = imports.entry(group).or_default();
+
+            for (reference, _) in self.references(&schemas[*name]) {
+                if let Some((reference, other)) = groups.get_key_value(&*reference)
+                    && other != group
+                {
+                    imported.entry(other).or_default().insert(reference);
+                }
+            }
+        }
+
+        let reaches = |from: &str, to: &str| {
+            let mut stack = vec![from];
+            let mut seen = BTreeSet::new();
+
+            while let Some(group) = stack.pop() {
+                if group == to {
+                    return true;
+                }
+                if seen.insert(group) {
+                    stack.extend(imports[group].keys().copied());
+                }
+            }
+
+            false
+        };
+        let is_cyclic = |from: &str, to: &str| reaches(from, to) && reaches(to, from);
+
+        let mut types = (!self.0.output.is_typescript()).then(|| self.types_parser());
+        let names = self.name_module(schemas, "").names;
+        if let Some(types) = &mut types {
+            types.name_module(schemas);
+        }
+
+        let order = self.module_order(schemas);
+        let mut files = BTreeMap::new();
+
+        for (file, imported) in &imports {
+            let declarations: Vec<_> = order
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, name)| groups[name.as_str()] == *file)
+                .collect();
+            let cyclic: Vec<_> = groups
+                .iter()
+                .filter(|(_, group)| group != file && is_cyclic(file, group))
+                .map(|(name, _)| (String::from(*name), usize::MAX))
+                .collect();
+            let pending = declarations
+                .iter()
+                .map(|(position, name)| ((*name).clone(), *position))
+                .chain(cyclic)
+                .collect();
+
+            let library = self.0.backend.import();
+            let mut header: Vec<_> = library
+                .map(|import| self.0.output.library_import(import, false))
+                .into_iter()
+                .collect();
+            let mut header_types: Vec<_> = library
+                .map(|import| self.0.output.library_import(import, true))
+                .into_iter()
+                .collect();
+            // TypeScript only declares types
+            let is_type = matches!(self.0.config.target, Target::TypeScript);
+            let mut file_names = names.clone();
+
+            for (other, imported) in imported {
+                let declared: Vec<_> =
+                    imported.iter().map(|name| names[*name].as_str()).collect();
+                let binding = self
+                    .0
+                    .output
+                    .sibling_binding(other)
+                    .filter(|_| is_cyclic(file, other));
+
+                // the declarations are used through
+                // the binding of their module
+                if let Some(binding) = &binding {
+                    for name in imported {
+                        file_names.insert(
+                            String::from(*name),
+                            format!("{binding}.{}", names[*name]),
+                        );
+                    }
+                }
+
+                header.push(self.0.output.sibling_import(
+                    other,
+                    &declared,
+                    binding.as_deref(),
+                    is_type,
+                ));
+                if let Some(types) = &types {
+                    let declared: Vec<_> = imported
+                        .iter()
+                        .flat_map(|name| types.declared(name))
+                        .collect();
+                    let declared: Vec<_> = declared.iter().map(String::as_str).collect();
+                    header_types
+                        .push(self.0.output.sibling_import(other, &declared, None, true));
+                }
+            }
+
+            let file_module = Module {
+                names: file_names,
+                pending,
+                ..Default::default()
+            };
+            let parts = ModuleParts {
+                header: (!header.is_empty()).then(|| header.join("\n")),
+                footer: self.exports(&file_module, &declarations),
+                generics: &generics,
+                schemas,
+                declarations: declarations.clone(),
+            };
+            let mut module = String::new();
+            let written = self.write_module(parts, &file_module, None, &mut module, Ok);
+            self.finish_module(file_module);
+            written?;
+            files.insert(format!("{file}{}", self.0.output.extension()), module);
+
+            if let Some(types) = &types {
+                let parts = ModuleParts {
+                    header: (!header_types.is_empty()).then(|| header_types.join("\n")),
+                    footer: None,
+                    generics: &generics,
+                    schemas,
+                    declarations,
+                };
+                let mut module = String::new();
+                types.write_module(parts, &*self.0.backend, &mut module)?;
+                files.insert(format!("{file}.d.ts"), module);
+            }
+        }
+
+        files.insert(
+            format!("{INDEX}{}", self.0.output.extension()),
+            self.0.output.index(imports.keys().copied(), false),
+        );
+        if types.is_some() {
+            files.insert(
+                format!("{INDEX}.d.ts"),
+                self.0.output.index(imports.keys().copied(), true),
+            );
+        }
+
+        Ok(files)
+    }
+
+    /// Fail for outputs which can't be outputted as
+    /// the output mode
+    fn check_output(&self) -> Result<(), Error> {
+        match self.0.config.target {
+            Target::TypeScript => self.0.output.require_typescript(
+                "Output: TypeScript types can only be outputted as a .ts module",
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// The parsers of the TypeScript types of a
+    /// module, for its declaration file
+    fn types_parser(&self) -> Types {
+        let parser = |backend: Box<dyn Backend>| {
+            Parser::with_backend(self.0.config, backend)
+                .with_naming(self.0.naming.clone())
+        };
+
+        Types {
+            json: (parser(Box::new(TypeScriptBackend)), Module::default()),
+            outputs: self
+                .0
+                .config
+                .codecs
+                .then(|| (parser(Box::new(TypeScriptOutputBackend)), Module::default())),
+        }
+    }
+
+    /// Find the generic factories of the schemas, and
+    /// the schemas of the module with them
+    fn module_generics<'a>(
+        &self,
+        schemas: &'a BTreeMap<String, Value>,
+    ) -> (Generics, Cow<'a, BTreeMap<String, Value>>) {
+        let generics = match self.0.config.generic_factories {
+            true => Generics::new(schemas),
+            false => Generics::default(),
+        };
+        let module = match generics.factories.is_empty() {
+            true => Cow::Borrowed(schemas),
+            false => Cow::Owned(generics.module(schemas)),
+        };
+
+        (generics, module)
+    }
+
+    /// Name the declarations of the module, with
+    /// `suffix` after each name
+    fn name_module(&self, schemas: &BTreeMap<String, Value>, suffix: &str) -> Module {
+        let mut names = self.0.naming.names(schemas.keys(), &[]);
+        names
+            .values_mut()
+            .for_each(|identifier| identifier.push_str(suffix));
+
+        Module {
+            names,
+            ..Default::default()
+        }
+    }
+
+    /// The exports at the end of a module with
+    /// `declarations`, if they are not exported in
+    /// place
+    fn exports(
+        &self,
+        module: &Module,
+        declarations: &[(usize, &String)],
+    ) -> Option<String> {
+        let names: Vec<_> = declarations
+            .iter()
+            .map(|(_, name)| self.0.declaration_name(name, module.scope()))
+            .collect();
+
+        self.0.output.exports(&names)
+    }
+
+    /// Report the problems found in the module, in
+    /// the order of its declarations
+    pub(crate) fn finish_module(&self, module: Module) {
+        let mut diagnostics = module
+            .diagnostics
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner());
+        // the threads of rayon report in the order they
+        // finish
+        diagnostics.sort_by_key(|(position, _)| *position);
+
+        self.0
+            .diagnostics
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .extend(diagnostics.into_iter().map(|(_, diagnostic)| diagnostic));
+    }
+
+    /// Parse the declaration of the schema `name` of
+    /// a module, and the parameters of a factory
+    fn module_declaration(
+        &self,
+        name: &str,
+        generics: &Generics,
+        schemas: &BTreeMap<String, Value>,
+        scope: Scope<'_>,
+    ) -> Result<(Js, Vec<Parameter>), Error> {
+        let declaration = self.0.declaration_name(name, scope);
+        let export = self.0.output.is_exported_in_place();
+
+        if let Some((template, count)) = generics.factories.get(name) {
+            let schema: Schema = serde_json::from_value(template.clone())?;
+            let parameters =
+                generics::parameters(*count, |identifier| scope.is_taken(identifier));
+            // without schemas, e.g. in TypeScript, the
+            // template references the types
+            let references: Vec<_> = parameters
+                .iter()
+                .map(|parameter| match self.0.backend.schema_type("any", "any") {
+                    Some(_) => parameter.value.clone(),
+                    None => parameter.type_name.clone(),
+                })
+                .collect();
+            let generic = self.0.backend.generic(
+                &declaration,
+                &parameters,
+                self.0.parse_schema(&schema, Scope {
+                    parameters: &references,
+                    ..scope
+                })?,
+                template.get("description").and_then(Value::as_str),
+                self.0.output.is_typescript(),
+                export,
+            )?;
+
+            return Ok((generic, parameters));
+        }
+
+        if let Some((factory, arguments)) = generics.instances.get(name) {
+            let arguments = arguments
+                .iter()
+                .map(|argument| {
+                    self.0
+                        .parse_schema(&serde_json::from_value(argument.clone())?, scope)
+                })
+                .collect::<Result<_, Error>>()?;
+            let instance = self
+                .0
+                .backend
+                .instantiate(&self.0.declaration_name(factory, scope), arguments)?;
+            let instance = self.0.backend.declaration(
+                &declaration,
+                instance,
+                schemas[name].get("description").and_then(Value::as_str),
+                export,
+            )?;
+
+            return Ok((instance, Vec::new()));
+        }
+
+        let schema: Schema = serde_json::from_value(schemas[name].clone())?;
+        Ok((
+            self.0
+                .parse_declaration(&declaration, &schema, export, scope)?,
+            Vec::new(),
+        ))
+    }
+
+    /// Write the header, the declarations and the
+    /// footer of a module into `writer`
+    ///
+    /// With `declared`, the declarations are types
+    /// of a `.d.ts` file, each followed by the type
+    /// of its output and the declaration of its
+    /// schema.
+    fn write_module(
+        &self,
+        parts: ModuleParts<'_>,
+        module: &Module,
+        declared: Option<Declared<'_>>,
+        writer: &mut impl Write,
+        format: impl Fn(Js) -> JsResult + Sync,
+    ) -> Result<(), Error> {
+        let ModuleParts {
+            header,
+            footer,
+            generics,
+            schemas,
+            declarations,
+        } = parts;
+
+        let parse = |&(position, name): &(usize, &String)| {
+            let scope = Scope {
+                module: Some(module),
+                position,
+                parameters: &[],
+            };
+            let (declaration, parameters) =
+                self.module_declaration(name, generics, schemas, scope)?;
+            let Some(declared) = declared else {
+                return format(declaration);
+            };
+
+            let identifier = self.0.declaration_name(name, scope);
+            let mut parts = vec![declaration];
+            let output = match declared.outputs {
+                Some((outputs, outputs_module)) => {
+                    let scope = Scope {
+                        module: Some(outputs_module),
+                        position,
+                        parameters: &[],
+                    };
+                    parts.extend([
+                        Js::from("\n"),
+                        outputs
+                            .module_declaration(name, generics, schemas, scope)?
+                            .0,
+                    ]);
+                    outputs.0.declaration_name(name, scope)
+                },
+                None => identifier.clone(),
+            };
+            if let Some(schema) =
+                declared.backend.declared(&identifier, &output, &parameters)
+            {
+                parts.extend([Js::from("\n"), Js::from(schema)]);
+            }
+
+            format(Js::concat(parts))
+        };
+
+        let mut separator = "";
+        let mut write = |declaration: Js| {
+            writer.write_str(separator)?;
+            declaration.write_to(writer)?;
+            if !declaration.ends_with('\n') {
+                writer.write_char('\n')?;
+            }
+            separator = "\n";
+
+            Ok::<_, Error>(())
+        };
+
+        if let Some(header) = header {
+            write(format(header.into())?)?;
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            declarations
+                .par_iter()
+                .map(parse)
+                .collect::<Result<Vec<Js>, _>>()?
+                .into_iter()
+                .try_for_each(&mut write)?;
+        }
+        #[cfg(not(feature = "rayon"))]
+        declarations
+            .iter()
+            .try_for_each(|declaration| write(parse(declaration)?))?;
+
+        match footer {
+            Some(footer) => write(format(footer.into())?),
+            None => Ok(()),
+        }
+    }
+
+    /// Order the schemas, so references come first
+    fn module_order<'a>(&self, schemas: &'a BTreeMap<String, Value>) -> Vec<&'a String> {
+        let mut order = Vec::with_capacity(schemas.len());
+        let mut visited = BTreeSet::new();
+        let mut visiting = BTreeSet::new();
+
+        for name in schemas.keys() {
+            self.visit(name, schemas, &mut visiting, &mut visited, &mut order);
+        }
+
+        order
+    }
+
+    fn visit<'a>(
+        &self,
+        name: &'a String,
+        schemas: &'a BTreeMap<String, Value>,
+        visiting: &mut BTreeSet<&'a String>,
+        visited: &mut BTreeSet<&'a String>,
+        order: &mut Vec<&'a String>,
+    ) {
+        if visited.contains(name) || !visiting.insert(name) {
+            return;
+        }
+
+        let references = self.references(&schemas[name]);
+        let mut references: Vec<_> = references.into_iter().collect();
+        // direct references are visited first
+        references.sort_by_key(|(_, direct)| !*direct);
+
+        for (reference, direct) in references {
+            let Some((reference, _)) = schemas.get_key_value(&*reference) else {
+                continue;
+            };
+
+            // a cycle has to be lazy inside of a property
+            // or an item, not e.g. in a union
+            if !direct && self.reaches_directly(reference, schemas, visiting) {
+                continue;
+            }
+
+            self.visit(reference, schemas, visiting, visited, order);
+        }
+
+        visiting.remove(name);
+        visited.insert(name);
+        order.push(name);
+    }
+
+    /// Check if the schema reaches one of `targets`
+    /// only with direct references
+    fn reaches_directly(
+        &self,
+        name: &str,
+        schemas: &BTreeMap<String, Value>,
+        targets: &BTreeSet<&String>,
+    ) -> bool {
+        let mut stack = vec![Cow::Borrowed(name)];
+        let mut seen = BTreeSet::new();
+
+        while let Some(name) = stack.pop() {
+            if targets.iter().any(|target| **target == name) {
+                return true;
+            }
+
+            let Some((_, schema)) = schemas
+                .get_key_value(&*name)
+                .filter(|(name, _)| seen.insert(*name))
+            else {
+                continue;
+            };
+
+            stack.extend(
+                self.references(schema)
+                    .into_iter()
+                    .filter(|(_, direct)| *direct)
+                    .map(|(reference, _)| reference),
+            );
+        }
+
+        false
+    }
+
+    /// Get the names of the referenced schemas, and
+    /// if they are referenced directly instead of in
+    /// a property or an item
+    fn references<'a>(&self, value: &'a Value) -> BTreeMap<Cow<'a, str>, bool> {
+        let mut references = BTreeMap::new();
+        self.collect_references(value, true, &mut references);
+        references
+    }
+
+    fn collect_references<'a>(
+        &self,
+        value: &'a Value,
+        direct: bool,
+        references: &mut BTreeMap<Cow<'a, str>, bool>,
+    ) {
+        match value {
+            Value::Object(object) => {
+                if let Some(name) = object
+                    .get("$ref")
+                    .and_then(|reference| reference.as_str())
+                    .and_then(|reference| self.0.reference_name(reference))
+                {
+                    *references.entry(name).or_default() |= direct;
+                }
+
+                for (key, value) in object {
+                    let direct = direct
+                        && !matches!(
+                            key.as_str(),
+                            "properties"
+                                | "patternProperties"
+                                | "additionalProperties"
+                                | "items"
+                                | "additionalItems"
+                        );
+
+                    self.collect_references(value, direct, references);
+                }
+            },
+            Value::Array(values) =>
+                for value in values {
+                    self.collect_references(value, direct, references);
+                },
+            _ => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_module_concurrent() {
+        use serde_json::json;
+
+        use crate::{
+            Diagnostic,
+            Parser,
+            naming::{Case, Naming},
+        };
+
+        // the names of one document collide, the ones
+        // of the other don't
+        let document = |name: &str, other: &str| {
+            json!({
+                "components": {
+                    "schemas": {
+                        name: {
+                            "type": "object",
+                            "properties": {
+                                "next": { "$ref": format!("#/components/schemas/{other}") },
+                                "format": { "type": "string", "format": name },
+                            },
+                        },
+                        other: {
+                            "type": "array",
+                            "items": { "$ref": format!("#/components/schemas/{name}") },
+                        },
+                    },
+                },
+            })
+        };
+        let documents = [document("User", "user"), document("Order", "Item")];
+
+        let parser = Parser::default()
+            .with_naming(Naming::default().case(Case::Pascal).prefix("z"));
+        let expected: Vec<_> = documents
+            .iter()
+            .map(|document| parser.parse_openapi(document).unwrap())
+            .collect();
+        parser.take_diagnostics();
+
+        std::thread::scope(|threads| {
+            for (document, expected) in documents.iter().zip(&expected) {
+                let parser = &parser;
+                threads.spawn(move || {
+                    for _ in 0..32 {
+                        assert_eq!(expected, &parser.parse_openapi(document).unwrap());
+                    }
+                });
+            }
+        });
+
+        assert!(expected[0].contains("export const zUser2 = "));
+        let diagnostics = parser.take_diagnostics();
+        assert_eq!(64, diagnostics.len());
+        assert!(
+            diagnostics
+                .iter()
+                .all(|diagnostic| matches!(diagnostic, Diagnostic::UnknownFormat(_)))
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_module_rayon() {
+        use std::collections::BTreeMap;
+
+        use serde_json::{Value, json};
+
+        use crate::{Diagnostic, Parser};
+
+        let schemas: BTreeMap<String, Value> = (0..64)
+            .map(|i| {
+                (format!("Schema{i}"), json!({
+                    "type": "object",
+                    "required": ["format"],
+                    "properties": {
+                        "format": { "type": "string", "format": format!("format-{i}") },
+                        "next": { "$ref": format!("#/definitions/Schema{}", (i + 1) % 64) },
+                    },
+                }))
+            })
+            .collect();
+
+        let parse = |threads| {
+            let parser = Parser::default();
+            let module = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| parser.parse_module(&schemas, |_, _| Ok(())))
+                .unwrap();
+
+            (module, parser.take_diagnostics())
+        };
+
+        // one thread parses the declarations in order
+        let (sequential, sequential_diagnostics) = parse(1);
+        let (parallel, parallel_diagnostics) = parse(8);
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(sequential_diagnostics, parallel_diagnostics);
+        assert!(matches!(
+            parallel_diagnostics.first(),
+            Some(Diagnostic::UnknownFormat(_))
+        ));
+        assert_eq!(64, parallel_diagnostics.len());
+    }
+}
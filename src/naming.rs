@@ -0,0 +1,236 @@
+//! The names of the declarations of a module
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::module::identifier;
+
+/// How the name of a schema is cased
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Keep the name, e.g. `Page_for_User`
+    #[default]
+    Preserve,
+    /// e.g. `PageForUser`
+    Pascal,
+    /// e.g. `pageForUser`
+    Camel,
+    /// e.g. `page_for_user`
+    Snake,
+}
+
+impl Case {
+    /// Split a name into its words, at anything but
+    /// letters and digits, and before an uppercase
+    /// letter following a lowercase one
+    fn words(name: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut previous_lowercase = false;
+
+        for c in name.chars() {
+            if !c.is_ascii_alphanumeric() {
+                words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+                previous_lowercase = false;
+                continue;
+            }
+
+            if c.is_ascii_uppercase() && previous_lowercase {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+            word.push(c);
+        }
+        words.extend((!word.is_empty()).then_some(word));
+
+        words
+    }
+
+    /// Convert a name into the case
+    pub fn convert(self, name: &str) -> String {
+        let capitalize = |word: &str| {
+            let mut word = word.to_owned();
+            word[..1].make_ascii_uppercase();
+            word
+        };
+
+        match self {
+            Case::Preserve => name.to_owned(),
+            Case::Pascal => Self::words(name)
+                .iter()
+                .map(|word| capitalize(word))
+                .collect(),
+            Case::Camel => Self::words(name)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_ascii_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Case::Snake => Self::words(name)
+                .iter()
+                .map(|word| word.to_ascii_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        }
+    }
+}
+
+/// How the declarations of a module are named,
+/// e.g. `UserSchema` or `zUser` for the schema
+/// `User`
+///
+/// ```
+/// # use schemars_to_zod::{Parser, naming::{Case, Naming}};
+/// let parser = Parser::default().with_naming(
+///     Naming::default()
+///         .case(Case::Pascal)
+///         .suffix("Schema")
+///         .rename("Page_for_User", "UserPage"),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Naming {
+    prefix: String,
+    suffix: String,
+    case: Case,
+    renames: BTreeMap<String, String>,
+}
+
+impl Naming {
+    /// Add a prefix to every name, e.g. `z`
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Add a suffix to every name, e.g. `Schema`
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Convert the case of every name, before the
+    /// prefix and suffix are added
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Use `to` for the schema `name`, without the
+    /// prefix, suffix and case
+    pub fn rename(mut self, name: impl Into<String>, to: impl Into<String>) -> Self {
+        self.renames.insert(name.into(), to.into());
+        self
+    }
+
+    /// The identifier of the declaration of the
+    /// schema `name`
+    pub fn name(&self, name: &str) -> String {
+        match self.renames.get(name) {
+            Some(renamed) => identifier(renamed),
+            None => identifier(&format!(
+                "{}{}{}",
+                self.prefix,
+                self.case.convert(name),
+                self.suffix
+            )),
+        }
+    }
+
+    /// The identifiers of the declarations of a
+    /// module, names which collide with each other or
+    /// with `reserved` get a number, e.g.
+    /// `PageForUser2`
+    ///
+    /// A name changed by the case conversion gets
+    /// the number, rather than one which already has
+    /// the case.
+    pub(crate) fn names<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a String>,
+        reserved: &[String],
+    ) -> BTreeMap<String, String> {
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort_by_key(|name| {
+            !self.renames.contains_key(*name) && self.case.convert(name) != **name
+        });
+        let mut used: BTreeSet<_> = reserved.iter().cloned().collect();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let base = self.name(name);
+                let mut identifier = base.clone();
+
+                for i in 2.. {
+                    if used.insert(identifier.clone()) {
+                        break;
+                    }
+                    identifier = format!("{base}{i}");
+                }
+
+                (name.clone(), identifier)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Case, Naming};
+    use crate::Parser;
+
+    #[test]
+    fn test_naming() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Page_for_User": {
+                        "type": "object",
+                        "required": ["items"],
+                        "properties": {
+                            "items": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/user" },
+                            },
+                        },
+                    },
+                    "PageForUser": { "type": "string" },
+                    "User-id": { "type": "integer" },
+                    "UserId": { "type": "string" },
+                    "user": { "type": "string" },
+                    "class": { "type": "boolean" },
+                },
+            },
+        });
+
+        let parser = Parser::default().with_naming(
+            Naming::default()
+                .case(Case::Pascal)
+                .prefix("z")
+                .rename("class", "class"),
+        );
+        let result = parser.parse_openapi(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                "export const zPageForUser = z.string();\n\n",
+                "export const zUser = z.string();\n\n",
+                r#"export const zPageForUser2 = z.object({ "items": z.array(zUser) });"#,
+                "\n\n",
+                "export const zUserId2 = z.int32();\n\n",
+                "export const zUserId = z.string();\n\n",
+                "export const class_ = z.boolean();\n",
+            ),
+            &result
+        );
+    }
+}
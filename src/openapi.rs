@@ -0,0 +1,434 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde_json::Value;
+
+use crate::{Error, Parser, ParserResult};
+#[cfg(feature = "pretty")]
+use crate::{PrettyConfig, backends::Js};
+
+impl Parser {
+    /// Parse all schemas of the components of an
+    /// OpenAPI 3.0 or 3.1 document into a module,
+    /// e.g. `export const <name> = <schema>;`
+    ///
+    /// References to other components use their
+    /// declaration, cyclic references are lazy.
+    pub fn parse_openapi(&self, document: &Value) -> ParserResult {
+        let mut module = String::new();
+        self.parse_openapi_into(document, &mut module)?;
+
+        Ok(module)
+    }
+
+    /// Parse all schemas of the components of an
+    /// OpenAPI document into `writer`, e.g. for large
+    /// documents
+    pub fn parse_openapi_into(
+        &self,
+        document: &Value,
+        writer: &mut impl Write,
+    ) -> Result<(), Error> {
+        self.parse_module_into(&self.openapi_schemas(document)?, writer, Ok)
+    }
+
+    /// Parse all schemas of the components of an
+    /// OpenAPI document into a file per group, where
+    /// `group` gives the group of a schema name, e.g.
+    /// the rust module of the type
+    ///
+    /// Returns the content of each file, and an
+    /// `index` re-exporting all of them, e.g.
+    /// `index.ts`. Outside of TypeScript every file
+    /// has a `.d.ts` declaration file. A group has to
+    /// be a js identifier, as it names the file.
+    ///
+    /// ```
+    /// # use schemars_to_zod::Parser;
+    /// # let document = serde_json::json!({});
+    /// let files = Parser::default().parse_openapi_files(&document, |name| {
+    ///     match name.split_once("_for_") {
+    ///         Some(_) => String::from("generic"),
+    ///         None => name.to_lowercase(),
+    ///     }
+    /// });
+    /// # let _ = files;
+    /// ```
+    pub fn parse_openapi_files(
+        &self,
+        document: &Value,
+        group: impl Fn(&str) -> String,
+    ) -> Result<BTreeMap<String, String>, Error> {
+        self.parse_module_files(&self.openapi_schemas(document)?, group)
+    }
+
+    /// Parse all schemas of the components of an
+    /// OpenAPI document into the `.d.ts` declaration
+    /// file of the module of `parse_openapi`, e.g.
+    /// for `OutputMode::EsmJs`
+    ///
+    /// Each schema is typed by the TypeScript type
+    /// of its json, like `Target::TypeScript`.
+    pub fn parse_openapi_types(&self, document: &Value) -> ParserResult {
+        let mut module = String::new();
+        self.parse_module_types(&self.openapi_schemas(document)?, &mut module)?;
+
+        Ok(module)
+    }
+
+    /// The normalized schemas of the components
+    pub(crate) fn openapi_schemas(
+        &self,
+        document: &Value,
+    ) -> Result<BTreeMap<String, Value>, Error> {
+        let mut schemas = BTreeMap::new();
+
+        if let Some(components) = document
+            .pointer("/components/schemas")
+            .and_then(|schemas| schemas.as_object())
+        {
+            for (name, schema) in components {
+                schemas.insert(name.clone(), self.0.normalize_openapi(schema)?);
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    /// Parse all schemas of the components of an
+    /// OpenAPI document into a module and prettify
+    /// it, one declaration at a time
+    #[cfg(feature = "pretty")]
+    pub fn parse_openapi_pretty(
+        &self,
+        document: &Value,
+        config: &PrettyConfig,
+    ) -> ParserResult {
+        let mut module = String::new();
+        self.parse_module_into(
+            &self.openapi_schemas(document)?,
+            &mut module,
+            |parsed| {
+                crate::pretty::format_js(
+                    &parsed.to_string(),
+                    self.0.output.extension(),
+                    config,
+                )
+                .map(Js::from)
+                .ok()
+                .ok_or(Error::PrettifyError)
+            },
+        )?;
+
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Config, Error, Parser, Target};
+
+    #[test]
+    fn test_openapi() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Dog" },
+                            { "$ref": "#/components/schemas/Cat" },
+                        ],
+                        "discriminator": {
+                            "propertyName": "petType",
+                            "mapping": {
+                                "dog": "#/components/schemas/Dog",
+                                "cat": "#/components/schemas/Cat",
+                            },
+                        },
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {
+                            "petType": { "type": "string" },
+                            "owner": { "type": "string", "nullable": true },
+                        },
+                        "example": { "petType": "dog" },
+                    },
+                    "Cat": {
+                        "type": "object",
+                        "required": ["petType", "lives"],
+                        "properties": {
+                            "petType": { "type": "string" },
+                            "lives": { "type": "integer", "minimum": 0, "exclusiveMinimum": true },
+                            "friend": { "$ref": "#/components/schemas/Pet" },
+                        },
+                    },
+                },
+            },
+        });
+
+        let parser = Parser::default();
+        let result = parser.parse_openapi(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const Cat = z.object({ "friend": z.optional(z.lazy(() => Pet)), "#,
+                r#""lives": z.int32().check(z.gt(0)), "petType": z.string() });"#,
+                "\n\n",
+                r#"export const Dog = z.object({ "owner": z.nullish(z.string()), "#,
+                r#""petType": z.string() });"#,
+                "\n\n",
+                r#"export const Pet = z.discriminatedUnion("petType", [z.extend(Dog, "#,
+                r#"{ "petType": z.literal("dog") }), z.extend(Cat, "#,
+                r#"{ "petType": z.literal("cat") })]);"#,
+                "\n",
+            ),
+            &result
+        );
+        crate::parsers::check_module(&result);
+    }
+
+    #[test]
+    fn test_openapi_implicit_mapping() {
+        let pet = json!({
+            "type": "object",
+            "required": ["petType"],
+            "properties": { "petType": { "type": "string" } },
+        });
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Dog" },
+                            { "$ref": "#/components/schemas/Cat" },
+                        ],
+                        "discriminator": { "propertyName": "petType" },
+                    },
+                    "Dog": pet,
+                    "Cat": pet,
+                },
+            },
+        });
+
+        let parser = Parser::default();
+        let result = parser.parse_openapi(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export const Cat = z.object({ "petType": z.string() });"#,
+                "\n\n",
+                r#"export const Dog = z.object({ "petType": z.string() });"#,
+                "\n\n",
+                r#"export const Pet = z.discriminatedUnion("petType", [z.extend(Dog, "#,
+                r#"{ "petType": z.literal("Dog") }), z.extend(Cat, "#,
+                r#"{ "petType": z.literal("Cat") })]);"#,
+                "\n",
+            ),
+            &result
+        );
+        crate::parsers::check_module(&result);
+    }
+
+    #[test]
+    fn test_openapi_files() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "orders": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Order" },
+                            },
+                        },
+                    },
+                    "Order": {
+                        "type": "object",
+                        "required": ["user"],
+                        "properties": {
+                            "user": { "$ref": "#/components/schemas/User" },
+                            "status": { "$ref": "#/components/schemas/Status" },
+                        },
+                    },
+                    "Status": { "type": "string", "enum": ["open", "closed"] },
+                },
+            },
+        });
+
+        let parser = Parser::default();
+        let files = parser
+            .parse_openapi_files(&document, |name| match name {
+                "User" => String::from("users"),
+                _ => String::from("orders"),
+            })
+            .unwrap();
+
+        assert_eq!(
+            vec!["index.ts", "orders.ts", "users.ts"],
+            files.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            concat!(
+                r#"export * from "./orders";"#,
+                "\n",
+                r#"export * from "./users";"#,
+                "\n",
+            ),
+            &files["index.ts"]
+        );
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n",
+                r#"import { User } from "./users";"#,
+                "\n\n",
+                r#"export const Status = z.enum(["open", "closed"]);"#,
+                "\n\n",
+                r#"export const Order = z.object({ "status": z.optional(Status), "#,
+                r#""user": z.lazy(() => User) });"#,
+                "\n",
+            ),
+            &files["orders.ts"]
+        );
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n",
+                r#"import { Order } from "./orders";"#,
+                "\n\n",
+                r#"export const User = z.object({ "name": z.string(), "#,
+                r#""orders": z.optional(z.array(z.lazy(() => Order))) });"#,
+                "\n",
+            ),
+            &files["users.ts"]
+        );
+    }
+
+    #[test]
+    fn test_openapi_files_cycle() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Dog" },
+                            { "$ref": "#/components/schemas/Cat" },
+                        ],
+                        "discriminator": {
+                            "propertyName": "petType",
+                            "mapping": {
+                                "dog": "#/components/schemas/Dog",
+                                "cat": "#/components/schemas/Cat",
+                            },
+                        },
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": { "petType": { "type": "string" } },
+                    },
+                    "Cat": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {
+                            "petType": { "type": "string" },
+                            "friend": { "$ref": "#/components/schemas/Pet" },
+                        },
+                    },
+                },
+            },
+        });
+
+        // the files of the pets and the cats import
+        // each other, so the cat is declared after
+        // the union of the pets
+        let parser = Parser::default();
+        let files = parser
+            .parse_openapi_files(&document, |name| match name {
+                "Cat" => String::from("cats"),
+                _ => String::from("pets"),
+            })
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import * as z from "zod/mini";"#,
+                "\n",
+                r#"import { Cat } from "./cats";"#,
+                "\n\n",
+                r#"export const Dog = z.object({ "petType": z.string() });"#,
+                "\n\n",
+                r#"export const Pet = z.discriminatedUnion("petType", [z.extend(Dog, "#,
+                r#"{ "petType": z.literal("dog") }), z.lazy(() => z.extend(Cat, "#,
+                r#"{ "petType": z.literal("cat") }))]);"#,
+                "\n",
+            ),
+            &files["pets.ts"]
+        );
+        crate::parsers::check_module(&format!(
+            concat!(
+                "{}{}",
+                "Pet.parse({{ petType: 'cat', friend: {{ petType: 'dog' }} }});\n",
+                "if (Pet.safeParse({{ petType: 'cat', friend: {{ petType: 'bird' }} }})",
+                ".success) throw new Error('bird');\n",
+            ),
+            files["pets.ts"], files["cats.ts"],
+        ));
+    }
+
+    #[test]
+    fn test_openapi_files_typescript() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "User": { "type": "string" },
+                    "Order": {
+                        "type": "object",
+                        "required": ["user"],
+                        "properties": {
+                            "user": { "$ref": "#/components/schemas/User" },
+                        },
+                    },
+                },
+            },
+        });
+
+        let parser = Parser::new(Config {
+            target: Target::TypeScript,
+            ..Default::default()
+        });
+        let files = parser
+            .parse_openapi_files(&document, |name| name.to_lowercase())
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import type { User } from "./user";"#,
+                "\n\n",
+                r#"export type Order = { "user": User; };"#,
+                "\n",
+            ),
+            &files["order.ts"]
+        );
+
+        for group in ["api/users", "user\"s", "2fa", ""] {
+            assert!(matches!(
+                parser.parse_openapi_files(&document, |_| String::from(group)),
+                Err(Error::SchemaError(_))
+            ));
+        }
+    }
+}
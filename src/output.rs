@@ -0,0 +1,358 @@
+//! The module system and the files of the output
+//! of a module
+
+use std::io;
+
+use crate::{Error, module::identifier};
+
+/// The kind of module outputted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// An ES module in TypeScript, `.ts`
+    #[default]
+    EsmTs,
+    /// An ES module in JavaScript, `.js`, typed by
+    /// a `.d.ts` declaration file
+    EsmJs,
+    /// A CommonJS module using `require`, `.js`,
+    /// typed by a `.d.ts` declaration file
+    CommonJs,
+}
+
+/// How a module is outputted
+///
+/// ```
+/// # use schemars_to_zod::{Parser, output::{Output, OutputMode}};
+/// let parser = Parser::default().with_output(
+///     Output::default()
+///         .mode(OutputMode::CommonJs)
+///         .import("@acme/shared/zod"),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Output {
+    mode: OutputMode,
+    import: Option<String>,
+}
+
+impl Output {
+    /// Output modules of `mode`
+    pub fn mode(mut self, mode: OutputMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Import the library from `specifier` instead,
+    /// e.g. `zod/v4/mini` or a package re-exporting
+    /// it
+    pub fn import(mut self, specifier: impl Into<String>) -> Self {
+        self.import = Some(specifier.into());
+        self
+    }
+
+    /// Check if the output is TypeScript, which may
+    /// use types
+    pub(crate) fn is_typescript(&self) -> bool { self.mode == OutputMode::EsmTs }
+
+    /// Fail for outputs which are only supported
+    /// as TypeScript
+    pub(crate) fn require_typescript(&self, err: &'static str) -> Result<(), Error> {
+        match self.is_typescript() {
+            true => Ok(()),
+            false => Err(Error::Unimplemented(err)),
+        }
+    }
+
+    /// The extension of a module
+    pub(crate) fn extension(&self) -> &'static str {
+        match self.mode {
+            OutputMode::EsmTs => ".ts",
+            OutputMode::EsmJs | OutputMode::CommonJs => ".js",
+        }
+    }
+
+    /// The extension of the specifier of a module,
+    /// which is required by node for an ES module
+    fn specifier_extension(&self) -> &'static str {
+        match self.mode {
+            OutputMode::EsmJs => ".js",
+            OutputMode::EsmTs | OutputMode::CommonJs => "",
+        }
+    }
+
+    /// The import of the library, from the binding,
+    /// e.g. `* as z`, and the default specifier of
+    /// the backend
+    ///
+    /// A declaration file only imports types.
+    pub(crate) fn library_import(
+        &self,
+        (binding, specifier): (&str, &str),
+        declarations: bool,
+    ) -> String {
+        let specifier = self.import.as_deref().unwrap_or(specifier);
+
+        match (self.mode, declarations) {
+            (_, true) => format!("import type {binding} from \"{specifier}\";"),
+            (OutputMode::CommonJs, false) => format!(
+                "const {} = require(\"{specifier}\");",
+                binding.strip_prefix("* as ").unwrap_or(binding)
+            ),
+            (OutputMode::EsmTs | OutputMode::EsmJs, false) =>
+                format!("import {binding} from \"{specifier}\";"),
+        }
+    }
+
+    /// The import of `names` from the module of a
+    /// sibling `file`, which is `binding` if the
+    /// module is only used once it is evaluated
+    pub(crate) fn sibling_import(
+        &self,
+        file: &str,
+        names: &[&str],
+        binding: Option<&str>,
+        declarations: bool,
+    ) -> String {
+        let specifier = format!("./{file}{}", self.specifier_extension());
+        let names = names.join(", ");
+
+        match (self.mode, declarations, binding) {
+            (_, true, _) => format!("import type {{ {names} }} from \"{specifier}\";"),
+            (OutputMode::CommonJs, false, Some(binding)) =>
+                format!("const {binding} = require(\"{specifier}\");"),
+            (OutputMode::CommonJs, false, None) =>
+                format!("const {{ {names} }} = require(\"{specifier}\");"),
+            (OutputMode::EsmTs | OutputMode::EsmJs, false, _) =>
+                format!("import {{ {names} }} from \"{specifier}\";"),
+        }
+    }
+
+    /// The binding of the module of a sibling `file`
+    /// in a CommonJS module, whose declarations are
+    /// used by it before it is evaluated
+    pub(crate) fn sibling_binding(&self, file: &str) -> Option<String> {
+        (self.mode == OutputMode::CommonJs).then(|| identifier(file))
+    }
+
+    /// Check if the declarations of a module are
+    /// exported in place, not at its end
+    pub(crate) fn is_exported_in_place(&self) -> bool {
+        self.mode != OutputMode::CommonJs
+    }
+
+    /// The exports of the declarations `names` at the
+    /// end of a CommonJS module
+    ///
+    /// The exports object is kept, so a module which
+    /// requires this one while it is evaluated sees
+    /// them once they are declared.
+    pub(crate) fn exports(&self, names: &[String]) -> Option<String> {
+        (self.mode == OutputMode::CommonJs)
+            .then(|| format!("Object.assign(exports, {{ {} }});", names.join(", ")))
+    }
+
+    /// The index re-exporting every one of `files`
+    pub(crate) fn index<'a>(
+        &self,
+        files: impl IntoIterator<Item = &'a str>,
+        declarations: bool,
+    ) -> String {
+        let files: Vec<_> = files
+            .into_iter()
+            .map(|file| format!("./{file}{}", self.specifier_extension()))
+            .collect();
+
+        match (self.mode, declarations) {
+            (OutputMode::CommonJs, false) => {
+                let files: Vec<_> = files
+                    .iter()
+                    .map(|file| format!("require(\"{file}\")"))
+                    .collect();
+                format!("Object.assign(exports, {});\n", files.join(", "))
+            },
+            _ => files
+                .iter()
+                .map(|file| format!("export * from \"{file}\";\n"))
+                .collect(),
+        }
+    }
+}
+
+/// Adapts an `io::Write`, e.g. a file, for the
+/// `parse_*_into` functions taking a `fmt::Write`
+///
+/// ```no_run
+/// # use schemars_to_zod::{Parser, output::IoWriter};
+/// # let schema = schemars::schema::Schema::Bool(true);
+/// let mut writer = IoWriter::new(std::fs::File::create("schema.js").unwrap());
+/// Parser::default().parse_into(&schema, &mut writer).unwrap();
+/// writer.into_inner().unwrap();
+/// ```
+pub struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    /// Write into `inner`
+    pub fn new(inner: W) -> Self { Self { inner, error: None } }
+
+    /// The writer, or the error of the write which
+    /// failed, as `fmt::Write` can't return it
+    pub fn into_inner(self) -> io::Result<W> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl<W: io::Write> std::fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            std::fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{IoWriter, Output, OutputMode};
+    use crate::{Config, Parser};
+
+    fn document() -> serde_json::Value {
+        json!({
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "friend": { "$ref": "#/components/schemas/User" },
+                        },
+                    },
+                    "Role": { "type": "string", "enum": ["admin", "user"] },
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn test_output_common_js() {
+        let parser = Parser::default().with_output(
+            Output::default()
+                .mode(OutputMode::CommonJs)
+                .import("@acme/zod"),
+        );
+        let result = parser.parse_openapi(&document()).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"const z = require("@acme/zod");"#,
+                "\n\n",
+                r#"const Role = z.enum(["admin", "user"]);"#,
+                "\n\n",
+                r#"const User = z.object({ "friend": z.optional(z.lazy(() => User)), "#,
+                r#""name": z.string() });"#,
+                "\n\n",
+                "Object.assign(exports, { Role, User });\n",
+            ),
+            &result
+        );
+    }
+
+    #[test]
+    fn test_output_declarations() {
+        let parser =
+            Parser::default().with_output(Output::default().mode(OutputMode::EsmJs));
+        let result = parser.parse_openapi_types(&document()).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import type * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export type Role = "admin" | "user";"#,
+                "\n",
+                "export declare const Role: z.ZodMiniType<Role>;\n\n",
+                r#"export type User = { "friend"?: User; "name": string; };"#,
+                "\n",
+                "export declare const User: z.ZodMiniType<User>;\n",
+            ),
+            &result
+        );
+        crate::parsers::check_types("declarations", &result);
+    }
+
+    #[test]
+    fn test_output_declarations_codecs() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Account": {
+                        "type": "object",
+                        "required": ["balance"],
+                        "properties": {
+                            "balance": { "type": "string", "format": "int64" },
+                            "limit": { "type": "integer", "default": 0 },
+                        },
+                    },
+                },
+            },
+        });
+
+        let parser = Parser::new(Config {
+            codecs: true,
+            ..Default::default()
+        })
+        .with_output(Output::default().mode(OutputMode::EsmJs));
+        let result = parser.parse_openapi_types(&document).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"import type * as z from "zod/mini";"#,
+                "\n\n",
+                r#"export type Account = { "balance": string; "limit"?: number; };"#,
+                "\n",
+                r#"export type AccountOutput = { "balance": bigint; "limit": number; };"#,
+                "\n",
+                "export declare const Account: z.ZodMiniType<AccountOutput, Account>;\n",
+            ),
+            &result
+        );
+        crate::parsers::check_types("declarations-codecs", &result);
+    }
+
+    #[test]
+    fn test_parse_into() {
+        let schema = serde_json::from_value(json!({
+            "type": "object",
+            "required": ["inner"],
+            "properties": {
+                "inner": {
+                    "type": ["object", "null"],
+                    "properties": { "list": { "type": "array", "items": { "type": "string" } } },
+                },
+            },
+        }))
+        .unwrap();
+        let parser = Parser::default();
+
+        let mut result = String::from("const schema = ");
+        parser.parse_into(&schema, &mut result).unwrap();
+        assert_eq!(
+            format!("const schema = {}", parser.parse(&schema).unwrap()),
+            result
+        );
+
+        let mut writer = IoWriter::new(Vec::new());
+        parser.parse_openapi_into(&document(), &mut writer).unwrap();
+        assert_eq!(
+            parser.parse_openapi(&document()).unwrap().into_bytes(),
+            writer.into_inner().unwrap()
+        );
+    }
+}
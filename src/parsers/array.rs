@@ -1,10 +1,10 @@
-use schemars::schema::{SchemaObject, SingleOrVec};
+use schemars::schema::{Schema, SchemaObject, SingleOrVec};
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{Error, JsResult, ParserInner, Scope};
 
 impl ParserInner {
     /// Parse an array
-    pub fn parse_array(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_array(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
         let options_default = Default::default();
         let options = object.array.as_ref().unwrap_or(&options_default);
 
@@ -16,42 +16,28 @@ impl ParserInner {
 
         let array_parsed = if let Some(items) = &options.items {
             match items {
-                SingleOrVec::Single(schema) => {
-                    let mut res = format!("z.array({})", self.parse_schema(&*schema)?);
-                    let mut checks = Vec::new();
-
-                    if let Some(min_items) = options.min_items {
-                        checks.push(format!("z.minLength({min_items})"));
-                    }
-
-                    if let Some(max_items) = options.max_items {
-                        checks.push(format!("z.maxLength({max_items})"));
-                    }
-
-                    if !checks.is_empty() {
-                        res.push_str(&format!(".check({})", checks.join(", ")));
-                    }
-
-                    res
-                },
+                SingleOrVec::Single(schema) => self
+                    .backend
+                    .array(self.parse_schema(schema, scope)?, options)?,
                 SingleOrVec::Vec(schemas) => {
                     let mut schemas_parsed = Vec::with_capacity(schemas.len());
 
                     for schema in schemas {
-                        schemas_parsed.push(self.parse_schema(&schema)?);
+                        schemas_parsed.push(self.parse_schema(schema, scope)?);
                     }
 
-                    if let Some(additional) = &options.additional_items {
-                        let rest = self.parse_schema(&*additional)?;
+                    let rest = match options.additional_items.as_deref() {
+                        // no more items are allowed
+                        None | Some(Schema::Bool(false)) => None,
+                        Some(additional) => Some(self.parse_schema(additional, scope)?),
+                    };
 
-                        format!("z.tuple([{}], {})", schemas_parsed.join(", "), rest)
-                    } else {
-                        format!("z.tuple([{}])", schemas_parsed.join(", "))
-                    }
+                    self.backend.tuple(schemas_parsed, rest)?
                 },
             }
         } else {
-            String::from("z.array(z.never())")
+            self.backend
+                .array(self.backend.never()?, &Default::default())?
         };
 
         Ok(array_parsed)
@@ -87,5 +73,6 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/array.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "array");
     }
 }
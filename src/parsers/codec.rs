@@ -0,0 +1,134 @@
+use crate::{DateFormat, Error, JsResult, ParserInner};
+
+impl ParserInner {
+    /// Check if a string format is sent over the
+    /// wire in an encoded form and
+    /// `parse_codec` is safe to call
+    pub fn is_codec(&self, format: &str) -> bool {
+        match format {
+            "date-time" | "partial-date-time" | "date" =>
+                matches!(self.date_format_of(format), Some(DateFormat::Codec)),
+            "duration" | "byte" | "base64" | "int64" | "uint64" => self.config.codecs,
+            _ => false,
+        }
+    }
+
+    /// Parse a string format into a codec, e.g.
+    /// `z.codec`, which decodes the wire format and
+    /// encodes it back again
+    pub fn parse_codec(&self, format: &str) -> JsResult {
+        if !self.is_codec(format) {
+            return Err(Error::ForgotCheck(
+                "Codec: parse_codec requires a format supported by is_codec",
+            ));
+        }
+
+        self.backend.codec(format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, Utc};
+    use schemars::{
+        JsonSchema,
+        r#gen::SchemaGenerator,
+        schema::{InstanceType, Schema, SchemaObject},
+    };
+
+    use crate::{Config, DateFormat, Parser, test_helpers::generator};
+
+    fn int64_string(_: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some(String::from("int64")),
+            ..Default::default()
+        })
+    }
+
+    fn duration_string(_: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some(String::from("duration")),
+            ..Default::default()
+        })
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "camelCase")]
+    struct TestSchema {
+        birthday: NaiveDate,
+        created_at: DateTime<Utc>,
+        #[schemars(schema_with = "int64_string")]
+        balance: i64,
+        #[schemars(schema_with = "duration_string")]
+        timeout: u64,
+    }
+
+    #[test]
+    fn test_codec() {
+        let schema = generator().into_root_schema_for::<TestSchema>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::new(Config {
+            date_format: DateFormat::Codec,
+            codecs: true,
+            ..Default::default()
+        });
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"z.object({ "balance": z.codec(z.string().check(z.regex(/^-?\d+$/)), "#,
+                r#"z.bigint(), { decode: s => BigInt(s), encode: n => n.toString() }), "#,
+                r#""birthday": z.codec(z.iso.date(), z.date(), { decode: s => new Date(s), "#,
+                r#"encode: d => d.toISOString().slice(0, 10) }), "#,
+                r#""createdAt": z.codec(z.iso.datetime({ offset: true }), z.date(), "#,
+                r#"{ decode: s => new Date(s), encode: d => d.toISOString() }), "#,
+                r#""timeout": z.codec(z.string().check(z.regex(/^P(?:\d+D)?(?:T(?:\d+H)?"#,
+                r#"(?:\d+M)?(?:[\d.]+S)?)?$/)), z.int().check(z.nonnegative()), "#,
+                r#"{ decode: s => { const [, d = 0, h = 0, m = 0, sec = '0'] = "#,
+                r#"/P(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:([\d.]+)S)?)?/.exec(s); "#,
+                r#"const [whole, fraction = ''] = sec.split('.'); "#,
+                r#"return ((+d * 24 + +h) * 60 + +m) * 60000 + +whole * 1000 + "#,
+                r#"Math.round(+('0.' + fraction) * 1000); }, "#,
+                r#"encode: ms => 'PT' + (ms - ms % 1000) / 1000 + (ms % 1000 ? '.' + "#,
+                r#"String(ms % 1000).padStart(3, '0').replace(/0+$/, '') : '') + 'S' }) })"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+
+    #[test]
+    fn test_codec_duration() {
+        let schema = duration_string(&mut generator());
+        let parser = Parser::new(Config {
+            codecs: true,
+            ..Default::default()
+        });
+        let duration = parser.parse(&schema).unwrap();
+
+        // the durations are encoded exactly into the
+        // input, and negative, fractional or unsafe
+        // outputs are rejected
+        crate::parsers::check(format!(
+            concat!(
+                "const duration = {};\n",
+                "for (const [ms, s] of [[0, 'PT0S'], [5, 'PT0.005S'], [1500, 'PT1.5S'], ",
+                "[Number.MAX_SAFE_INTEGER, 'PT9007199254740.991S']]) {{\n",
+                "  if (z.encode(duration, ms) !== s || z.decode(duration, s) !== ms) ",
+                "throw new Error(s);\n",
+                "}}\n",
+                "if (z.decode(duration, 'P1DT2H0.0005S') !== 93600001) ",
+                "throw new Error('P1DT2H0.0005S');\n",
+                "for (const ms of [-1500, 0.5, 1e22]) {{\n",
+                "  if (z.safeEncode(duration, ms).success) throw new \
+                 Error(String(ms));\n",
+                "}}\n",
+            ),
+            duration
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use schemars::schema::{Schema, SchemaObject};
+
+use crate::{Diagnostic, JsResult, ParserInner, Scope, backends::content_text};
+
+impl ParserInner {
+    /// Check if the string contains embedded json
+    /// and `parse_content` is safe to call
+    pub fn is_content(&self, object: &SchemaObject) -> bool {
+        object.extensions.contains_key("contentSchema")
+            || object
+                .extensions
+                .get("contentMediaType")
+                .and_then(|media_type| media_type.as_str())
+                .is_some_and(|media_type| {
+                    media_type == "application/json" || media_type.ends_with("+json")
+                })
+    }
+
+    /// Parse a string containing embedded json,
+    /// which is decoded and then parsed with the
+    /// `contentSchema`
+    pub fn parse_content(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
+        let encoding = object
+            .extensions
+            .get("contentEncoding")
+            .and_then(|encoding| encoding.as_str());
+
+        // the content can't be decoded, so it stays
+        // a plain string
+        if let Some(encoding) = encoding
+            && content_text(Some(encoding)).is_err()
+        {
+            self.report(Diagnostic::UnknownFormat(encoding.to_owned()), scope);
+
+            let options_default = Default::default();
+            let options = object.string.as_ref().unwrap_or(&options_default);
+
+            return self.backend.string(None, options);
+        }
+
+        let inner = if let Some(content_schema) = object.extensions.get("contentSchema") {
+            let content_schema: Schema = serde_json::from_value(content_schema.clone())?;
+
+            self.parse_schema(&content_schema, scope)?
+        } else if self.config.prefer_unknown {
+            self.backend.unknown()?
+        } else {
+            self.backend.any()?
+        };
+
+        self.backend.content(encoding, inner, self.config.codecs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema::Schema;
+    use serde_json::json;
+
+    use crate::{Diagnostic, Parser};
+
+    #[test]
+    fn test_content() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "string",
+            "contentEncoding": "base64",
+            "contentMediaType": "application/json",
+            "contentSchema": { "type": "array", "items": { "type": "boolean" } },
+        }))
+        .unwrap();
+
+        let parser = Parser::default();
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                "z.pipe(z.pipe(z.base64(), z.transform((s, ctx) => { try { return ",
+                "JSON.parse(new TextDecoder().decode(Uint8Array.from(atob(s), c => ",
+                "c.charCodeAt(0)))); } catch { ctx.issues.push({ code: 'custom', ",
+                "message: 'Invalid JSON', input: s }); return z.NEVER; } })), ",
+                "z.array(z.boolean()))",
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+
+    #[test]
+    fn test_unknown_content_encoding() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "string",
+            "contentEncoding": "quoted-printable",
+            "contentMediaType": "application/json",
+            "maxLength": 64,
+        }))
+        .unwrap();
+
+        let parser = Parser::default();
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(parser.take_diagnostics(), vec![Diagnostic::UnknownFormat(
+            String::from("quoted-printable")
+        )]);
+        assert_eq!("z.string().check(z.maxLength(64))", &result);
+        crate::parsers::check(result);
+    }
+}
@@ -0,0 +1,319 @@
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+use crate::{DateFormat, Diagnostic, JsResult, ParserInner, Scope, backends::Js};
+
+impl ParserInner {
+    /// Check if the object has a default value and
+    /// `parse_default` is safe to call
+    pub fn has_default(&self, object: &SchemaObject) -> bool {
+        object
+            .metadata
+            .as_ref()
+            .is_some_and(|metadata| metadata.default.is_some())
+    }
+
+    /// Wrap an already parsed schema with its
+    /// default value
+    ///
+    /// Uses `z.prefault` if the schema transforms
+    /// its input, e.g. a string into a `Date`, as
+    /// the default is given in its json form.
+    /// Invalid defaults are reported and left out.
+    pub fn parse_default(
+        &self,
+        object: &SchemaObject,
+        parsed: Js,
+        scope: Scope<'_>,
+    ) -> JsResult {
+        let Some(default) = object
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.default.as_ref())
+        else {
+            return Ok(parsed);
+        };
+
+        if !self.is_valid_default(object, default) {
+            self.report(
+                Diagnostic::InvalidDefault(serde_json::to_string(default)?),
+                scope,
+            );
+
+            return Ok(parsed);
+        }
+
+        self.backend.default(
+            parsed,
+            default,
+            self.config.prefault || self.is_transformed(object),
+        )
+    }
+
+    /// Get the default value of the schema, if it
+    /// is valid and applied by `parse_default`
+    pub fn valid_default<'a>(&self, object: &'a SchemaObject) -> Option<&'a Value> {
+        object
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.default.as_ref())
+            .filter(|default| self.is_valid_default(object, default))
+    }
+
+    /// Check if the json value is accepted by the
+    /// zod schema parsed from this schema
+    ///
+    /// References and patterns are not checked.
+    pub fn is_valid_default(&self, object: &SchemaObject, value: &Value) -> bool {
+        if object.reference.is_some() {
+            return true;
+        }
+
+        if let Some(const_value) = &object.const_value {
+            return const_value == value;
+        }
+
+        if let Some(enum_values) = &object.enum_values {
+            return enum_values.contains(value);
+        }
+
+        if let Some(subschemas) = &object.subschemas {
+            let valid = |schema: &Schema| self.is_valid_default_schema(schema, value);
+
+            if let Some(all_of) = &subschemas.all_of
+                && !all_of.iter().all(valid)
+            {
+                return false;
+            }
+
+            if let Some(variants) =
+                subschemas.one_of.as_ref().or(subschemas.any_of.as_ref())
+                && !variants.is_empty()
+                && !variants.iter().any(valid)
+            {
+                return false;
+            }
+
+            if object.instance_type.is_none() {
+                return true;
+            }
+        }
+
+        let Some(instance_type) = &object.instance_type else {
+            return true;
+        };
+
+        let instance_type = match (instance_type, value) {
+            (SingleOrVec::Single(instance_type), _) => **instance_type,
+            (SingleOrVec::Vec(instance_types), Value::Null) =>
+                return instance_types.contains(&InstanceType::Null),
+            (SingleOrVec::Vec(instance_types), _) => {
+                let mut instance_types =
+                    instance_types.iter().filter(|t| **t != InstanceType::Null);
+
+                return instance_types.any(|instance_type| {
+                    let object = SchemaObject {
+                        instance_type: Some((*instance_type).into()),
+                        ..object.clone()
+                    };
+
+                    self.is_valid_default(&object, value)
+                });
+            },
+        };
+
+        match (instance_type, value) {
+            (InstanceType::Null, Value::Null)
+            | (InstanceType::Boolean, Value::Bool(_)) => true,
+            (InstanceType::Integer | InstanceType::Number, Value::Number(number)) => {
+                let Some(number) = number.as_f64() else {
+                    return false;
+                };
+
+                if instance_type == InstanceType::Integer && number.fract() != 0. {
+                    return false;
+                }
+
+                let Some(options) = &object.number else {
+                    return true;
+                };
+
+                options.minimum.is_none_or(|min| number >= min)
+                    && options.maximum.is_none_or(|max| number <= max)
+                    && options.exclusive_minimum.is_none_or(|min| number > min)
+                    && options.exclusive_maximum.is_none_or(|max| number < max)
+            },
+            (InstanceType::String, Value::String(string)) => {
+                // a json string is not a `Date`
+                if let Some(format) = object.format.as_deref()
+                    && matches!(
+                        self.date_format_of(format),
+                        Some(DateFormat::JsDate | DateFormat::DateToJson)
+                    )
+                {
+                    return false;
+                }
+
+                let Some(options) = &object.string else {
+                    return true;
+                };
+
+                let length = string.chars().count() as u32;
+
+                options.min_length.is_none_or(|min| length >= min)
+                    && options.max_length.is_none_or(|max| length <= max)
+            },
+            (InstanceType::Array, Value::Array(values)) => {
+                let Some(options) = &object.array else {
+                    return true;
+                };
+
+                let length = values.len() as u32;
+
+                if options.min_items.is_some_and(|min| length < min)
+                    || options.max_items.is_some_and(|max| length > max)
+                {
+                    return false;
+                }
+
+                match &options.items {
+                    None => values.is_empty(),
+                    Some(SingleOrVec::Single(schema)) => values
+                        .iter()
+                        .all(|value| self.is_valid_default_schema(schema, value)),
+                    Some(SingleOrVec::Vec(schemas)) =>
+                        values.iter().enumerate().all(|(i, value)| {
+                            match (schemas.get(i), options.additional_items.as_deref()) {
+                                (Some(schema), _) | (None, Some(schema)) =>
+                                    self.is_valid_default_schema(schema, value),
+                                (None, None) => false,
+                            }
+                        }),
+                }
+            },
+            (InstanceType::Object, Value::Object(values)) => {
+                let Some(options) = &object.object else {
+                    return true;
+                };
+
+                options.required.iter().all(|key| values.contains_key(key))
+                    && values.iter().all(|(key, value)| {
+                        match (
+                            options.properties.get(key),
+                            options.additional_properties.as_deref(),
+                        ) {
+                            (Some(schema), _) | (None, Some(schema)) =>
+                                self.is_valid_default_schema(schema, value),
+                            (None, None) => true,
+                        }
+                    })
+            },
+            _ => false,
+        }
+    }
+
+    fn is_valid_default_schema(&self, schema: &Schema, value: &Value) -> bool {
+        match schema {
+            Schema::Bool(bool) => *bool,
+            Schema::Object(object) => self.is_valid_default(object, value),
+        }
+    }
+
+    /// Check if the zod schema parsed from this
+    /// schema outputs something different than its
+    /// json input
+    pub fn is_transformed(&self, object: &SchemaObject) -> bool {
+        let is_transformed = |schema: &Schema| match schema {
+            Schema::Bool(_) => false,
+            Schema::Object(object) => self.is_transformed(object),
+        };
+
+        if let Some(format) = object.format.as_deref()
+            && (self.is_codec(format)
+                || matches!(self.date_format_of(format), Some(DateFormat::CoerceDate)))
+        {
+            return true;
+        }
+
+        if self.is_content(object) {
+            return true;
+        }
+
+        if let Some(subschemas) = &object.subschemas
+            && [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of]
+                .into_iter()
+                .flatten()
+                .flatten()
+                .any(is_transformed)
+        {
+            return true;
+        }
+
+        if let Some(array) = &object.array {
+            let items = match &array.items {
+                Some(SingleOrVec::Single(schema)) => is_transformed(schema),
+                Some(SingleOrVec::Vec(schemas)) => schemas.iter().any(is_transformed),
+                None => false,
+            };
+
+            if items
+                || array
+                    .additional_items
+                    .as_deref()
+                    .is_some_and(is_transformed)
+            {
+                return true;
+            }
+        }
+
+        if let Some(options) = &object.object
+            && (options.properties.values().any(is_transformed)
+                || options
+                    .additional_properties
+                    .as_deref()
+                    .is_some_and(is_transformed))
+        {
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema::Schema;
+    use serde_json::json;
+
+    use crate::{Diagnostic, Parser};
+
+    #[test]
+    fn test_default() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "default": 1 },
+                "since": { "type": "string", "format": "date", "default": "2020-01-01" },
+                "tags": { "type": "array", "items": { "type": "string" }, "default": [1] },
+            },
+            "default": { "count": 2 },
+        }))
+        .unwrap();
+
+        let parser = Parser::default();
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(parser.take_diagnostics(), vec![Diagnostic::InvalidDefault(
+            String::from("[1]")
+        )]);
+        assert_eq!(
+            concat!(
+                r#"z.prefault(z.object({ "count": z._default(z.int32(), 1), "#,
+                r#""since": z.prefault(z.coerce.date(), "2020-01-01"), "#,
+                r#""tags": z.optional(z.array(z.string())) }), {"count":2})"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+}
@@ -1,10 +1,10 @@
 use schemars::schema::SchemaObject;
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{Error, JsResult, ParserInner};
 
 impl ParserInner {
     /// Parse an enum
-    pub fn parse_enum(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_enum(&self, object: &SchemaObject) -> JsResult {
         let Some(enum_values) = &object.enum_values else {
             return Err(Error::ForgotCheck(
                 "Enum: parse_enum requires property enum_values",
@@ -12,21 +12,11 @@ impl ParserInner {
         };
 
         Ok(if let [only] = enum_values.as_slice() {
-            format!("z.literal({})", serde_json::to_string(only)?)
-        } else if enum_values.len() != 0 {
-            let mut converted = Vec::with_capacity(enum_values.len());
-
-            for value in enum_values {
-                converted.push(serde_json::to_string(value)?);
-            }
-
-            if !converted.is_empty() {
-                format!("z.enum([{}])", converted.join(", "))
-            } else {
-                String::from("z.string()")
-            }
+            self.backend.literal(only)?
+        } else if !enum_values.is_empty() {
+            self.backend.r#enum(enum_values)?
         } else {
-            String::from("z.never()")
+            self.backend.never()?
         })
     }
 }
@@ -60,5 +50,6 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/enum.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "enum");
     }
 }
@@ -1,6 +1,6 @@
 use schemars::schema::{InstanceType, SchemaObject};
 
-use crate::{ParserInner, ParserResult};
+use crate::{JsResult, ParserInner, Scope};
 
 impl ParserInner {
     /// Find the correct parser for an instance
@@ -9,15 +9,16 @@ impl ParserInner {
         &self,
         instance_type: InstanceType,
         object: &SchemaObject,
-    ) -> ParserResult {
+        scope: Scope<'_>,
+    ) -> JsResult {
         Ok(match instance_type {
-            InstanceType::Null => String::from("z.null()"),
-            InstanceType::Boolean => String::from("z.boolean()"),
+            InstanceType::Null => self.backend.null()?,
+            InstanceType::Boolean => self.backend.boolean()?,
             InstanceType::Number => self.parse_number(false, object)?,
             InstanceType::Integer => self.parse_number(true, object)?,
-            InstanceType::String => self.parse_string(object)?,
-            InstanceType::Object => self.parse_object(object)?,
-            InstanceType::Array => self.parse_array(object)?,
+            InstanceType::String => self.parse_string(object, scope)?,
+            InstanceType::Object => self.parse_object(object, scope)?,
+            InstanceType::Array => self.parse_array(object, scope)?,
         })
     }
 }
@@ -0,0 +1,342 @@
+use serde_json::{Map, Value};
+
+use crate::{Diagnostic, Error, ParserInner, Scope, parsers::percent_decode};
+
+/// The keywords only holding metadata, which may
+/// be given next to a `$ref`
+const METADATA_KEYWORDS: [&str; 7] = [
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+impl ParserInner {
+    /// Turn a draft-07 or draft 2020-12 json schema
+    /// into the draft-07 schema read by schemars
+    ///
+    /// Local references are inlined, so `$defs` and
+    /// `definitions` are removed.
+    pub fn normalize_json(&self, value: &Value) -> Result<Value, Error> {
+        self.normalize_json_inner(value, value, &mut Vec::new(), false)
+    }
+
+    /// Turn a schema of an OpenAPI 3.0 or 3.1
+    /// document into the draft-07 schema read by
+    /// schemars
+    ///
+    /// References are kept, as every schema of the
+    /// components is declared on its own. The
+    /// `nullable` flag is kept for `parse_schema_object`.
+    pub fn normalize_openapi(&self, value: &Value) -> Result<Value, Error> {
+        self.normalize_json_inner(value, value, &mut Vec::new(), true)
+    }
+
+    fn normalize_json_inner(
+        &self,
+        value: &Value,
+        root: &Value,
+        references: &mut Vec<String>,
+        openapi: bool,
+    ) -> Result<Value, Error> {
+        let Value::Object(object) = value else {
+            return Ok(value.clone());
+        };
+
+        if object.contains_key("$dynamicRef") || object.contains_key("$recursiveRef") {
+            return Err(Error::Unimplemented(
+                "Reference: $dynamicRef and $recursiveRef are not supported",
+            ));
+        }
+
+        if let Some(reference) = object.get("$ref")
+            && !openapi
+        {
+            let Some(pointer) = reference.as_str().and_then(|r| r.strip_prefix('#'))
+            else {
+                return Err(Error::Unimplemented(
+                    "Reference: only local references are supported",
+                ));
+            };
+
+            if references.iter().any(|r| r == pointer) {
+                return Err(Error::Unimplemented(
+                    "Reference: recursive references are not supported",
+                ));
+            }
+
+            // the pointer unescapes `~0` and `~1` itself
+            let Some(target) = root.pointer(&percent_decode(pointer)) else {
+                return Err(Error::SchemaError("Reference: the reference doesn't exist"));
+            };
+
+            references.push(pointer.to_owned());
+            let mut resolved =
+                self.normalize_json_inner(target, root, references, openapi)?;
+            references.pop();
+
+            // keywords next to the reference are added to
+            // the referenced schema, metadata replaces it
+            if let Value::Object(resolved) = &mut resolved {
+                for (key, value) in object {
+                    if key == "$ref" {
+                        continue;
+                    }
+
+                    if METADATA_KEYWORDS.contains(&key.as_str()) {
+                        resolved.insert(key.clone(), value.clone());
+                    } else if !resolved.contains_key(key) {
+                        let mut sibling = Map::new();
+                        sibling.insert(key.clone(), value.clone());
+
+                        if let Value::Object(sibling) = self.normalize_json_inner(
+                            &Value::Object(sibling),
+                            root,
+                            references,
+                            openapi,
+                        )? {
+                            resolved.extend(sibling);
+                        }
+                    }
+                }
+            }
+
+            return Ok(resolved);
+        }
+
+        let mut normalize =
+            |value: &Value| self.normalize_json_inner(value, root, references, openapi);
+        let mut normalized = Map::new();
+
+        for (key, value) in object {
+            let value = match key.as_str() {
+                "$defs" | "definitions" | "$schema" | "$id" | "$anchor" | "$comment" =>
+                    continue,
+                "example" | "xml" | "externalDocs" if openapi => continue,
+                // draft-04 and OpenAPI 3.0 use a flag for
+                // the minimum and maximum
+                "exclusiveMinimum" | "exclusiveMaximum" if value.is_boolean() => {
+                    let bound = if key == "exclusiveMinimum" {
+                        "minimum"
+                    } else {
+                        "maximum"
+                    };
+
+                    if value == &Value::Bool(true)
+                        && let Some(bound_value) = object.get(bound)
+                    {
+                        normalized.insert(key.clone(), bound_value.clone());
+                    }
+                    continue;
+                },
+                "minimum"
+                    if object.get("exclusiveMinimum") == Some(&Value::Bool(true)) =>
+                    continue,
+                "maximum"
+                    if object.get("exclusiveMaximum") == Some(&Value::Bool(true)) =>
+                    continue,
+                // draft-07 has the required dependencies in
+                // `dependencies`, next to the schemas
+                "dependencies"
+                    if value.as_object().is_some_and(|dependencies| {
+                        dependencies.values().all(Value::is_array)
+                    }) =>
+                {
+                    normalized.insert(String::from("dependentRequired"), value.clone());
+                    continue;
+                },
+                "dependentSchemas" | "dependencies" => {
+                    self.report(
+                        Diagnostic::IgnoredKeyword(key.clone()),
+                        Scope::default(),
+                    );
+                    continue;
+                },
+                // the rest of the items after `prefixItems`
+                "items" if object.contains_key("prefixItems") => {
+                    normalized.insert(String::from("additionalItems"), normalize(value)?);
+                    continue;
+                },
+                "prefixItems" => {
+                    normalized.insert(String::from("items"), normalize(value)?);
+                    continue;
+                },
+                "unevaluatedProperties" => {
+                    if !object.contains_key("additionalProperties") {
+                        normalized.insert(
+                            String::from("additionalProperties"),
+                            normalize(value)?,
+                        );
+                    }
+                    continue;
+                },
+                "unevaluatedItems" => {
+                    // `items` after `prefixItems` are the rest
+                    let has_rest = object.contains_key("additionalItems")
+                        || (object.contains_key("prefixItems")
+                            && object.contains_key("items"));
+
+                    if !has_rest {
+                        normalized
+                            .insert(String::from("additionalItems"), normalize(value)?);
+                    }
+                    continue;
+                },
+                "properties" | "patternProperties" => match value {
+                    Value::Object(properties) => {
+                        let mut normalized_properties = Map::new();
+
+                        for (key, value) in properties {
+                            normalized_properties.insert(key.clone(), normalize(value)?);
+                        }
+
+                        Value::Object(normalized_properties)
+                    },
+                    _ => value.clone(),
+                },
+                "allOf" | "anyOf" | "oneOf" | "items" => match value {
+                    Value::Array(schemas) => Value::Array(
+                        schemas
+                            .iter()
+                            .map(&mut normalize)
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    _ => normalize(value)?,
+                },
+                "additionalProperties"
+                | "additionalItems"
+                | "contains"
+                | "propertyNames"
+                | "not"
+                | "if"
+                | "then"
+                | "else"
+                | "contentSchema" => normalize(value)?,
+                _ => value.clone(),
+            };
+
+            normalized.insert(key.clone(), value);
+        }
+
+        Ok(Value::Object(normalized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Diagnostic, Parser};
+
+    #[test]
+    fn test_json() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["kind", "point", "user"],
+            "properties": {
+                "kind": { "const": "user" },
+                "point": {
+                    "type": "array",
+                    "prefixItems": [{ "type": "number" }, { "type": "number" }],
+                    "items": false,
+                },
+                "user": { "$ref": "#/$defs/User", "description": "The user" },
+            },
+            "unevaluatedProperties": false,
+            "dependentSchemas": { "user": { "required": ["kind"] } },
+            "$defs": {
+                "User": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "nickname": { "type": ["string", "null"] },
+                    },
+                },
+            },
+        });
+
+        let parser = Parser::default();
+        let result = parser.parse_json(&schema).unwrap();
+
+        assert_eq!(parser.take_diagnostics(), vec![Diagnostic::IgnoredKeyword(
+            String::from("dependentSchemas")
+        )]);
+        assert_eq!(
+            concat!(
+                r#"z.object({ "kind": z.literal("user"), "point": "#,
+                r#"z.tuple([z.float64(), z.float64()]), "user": z.object({ "#,
+                r#""name": z.string(), "nickname": z.nullish(z.string()) }) })"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+
+    #[test]
+    fn test_dependent_required() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "card": { "type": "string" },
+                "billing": { "$ref": "#/$defs/a~1b%20c" },
+            },
+            "dependentRequired": { "card": ["billing"] },
+            "$defs": { "a/b c": { "type": "string" } },
+        });
+
+        let parser = Parser::default();
+        let result = parser.parse_json(&schema).unwrap();
+
+        assert_eq!(parser.take_diagnostics(), vec![]);
+        assert_eq!(
+            concat!(
+                r#"z.object({ "billing": z.optional(z.string()), "#,
+                r#""card": z.optional(z.string()) }).check(z.refine(o => "#,
+                r#"Object.entries({"card":["billing"]}).every(([k, r]) => !(k in o) "#,
+                r#"|| r.every(d => d in o))))"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+
+    #[cfg(feature = "schemars1")]
+    #[derive(schemars1::JsonSchema)]
+    #[schemars(crate = "schemars1")]
+    #[allow(dead_code)]
+    struct TestSchema {
+        name: String,
+        friends: Vec<TestFriend>,
+    }
+
+    #[cfg(feature = "schemars1")]
+    #[derive(schemars1::JsonSchema)]
+    #[schemars(crate = "schemars1")]
+    #[allow(dead_code)]
+    struct TestFriend {
+        name: String,
+    }
+
+    #[cfg(feature = "schemars1")]
+    #[test]
+    fn test_schemars1() {
+        let schema = schemars1::schema_for!(TestSchema);
+
+        let parser = Parser::default();
+        let result = parser.parse_schemars1(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"z.object({ "friends": z.array(z.object({ "name": z.string() })), "#,
+                r#""name": z.string() })"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+}
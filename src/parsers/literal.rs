@@ -1,6 +1,6 @@
 use schemars::schema::SchemaObject;
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{Error, JsResult, ParserInner};
 
 impl ParserInner {
     /// Check if the object is a literal and
@@ -11,11 +11,11 @@ impl ParserInner {
     }
 
     /// Parse a literal
-    pub fn parse_literal(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_literal(&self, object: &SchemaObject) -> JsResult {
         Ok(if let Some(literal) = &object.const_value {
-            format!("z.literal({})", serde_json::to_string(literal)?)
+            self.backend.literal(literal)?
         } else if let Some([only]) = object.enum_values.as_ref().map(|v| v.as_slice()) {
-            format!("z.literal({})", serde_json::to_string(only)?)
+            self.backend.literal(only)?
         } else {
             return Err(Error::ForgotCheck(
                 "Literal: has to have the const_value property or the enum_values \
@@ -1,17 +1,91 @@
+/// Parse the schema of a snapshot test with the
+/// other targets, compare it to their snapshots in
+/// `tests/<target>/<name>.ts` and evaluate it
+#[cfg(test)]
+macro_rules! check_targets {
+    ($parser:expr, $schema:expr, $name:literal) => {
+        for (target, import, snapshot) in [
+            (
+                crate::Target::ArkType,
+                crate::ARKTYPE_IMPORT,
+                include_str!(concat!("../../tests/arktype/", $name, ".ts")),
+            ),
+            (
+                crate::Target::Effect,
+                crate::EFFECT_IMPORT,
+                include_str!(concat!("../../tests/effect/", $name, ".ts")),
+            ),
+        ] {
+            let parser = crate::Parser::new(crate::Config {
+                target,
+                ..$parser.0.config
+            });
+            let result = parser.parse_pretty_default($schema).unwrap();
+
+            assert_eq!(snapshot, &result, "{} {}", $name, import);
+            crate::parsers::check_with(import, result);
+        }
+    };
+}
+
 mod array;
+mod codec;
+mod content;
+mod default;
 mod r#enum;
 mod instance_type;
+mod json;
 mod literal;
 mod number;
 mod object;
+mod reference;
 mod schema;
 mod schema_object;
 mod string;
 mod union;
 
+pub(crate) use reference::{percent_decode, unescape_pointer};
+
+#[cfg(test)]
+pub(crate) fn check(schema: String) { check_with(crate::ZOD_IMPORT, schema) }
+
+/// Evaluate a zod module in node, without its
+/// imports and the `export` keyword at the start
+/// of its declarations
+#[cfg(test)]
+pub(crate) fn check_module(module: &str) { check_module_with(crate::ZOD_IMPORT, module) }
+
+/// Evaluate a module in node, like `check_module`,
+/// after the `import` of its library
+#[cfg(test)]
+pub(crate) fn check_module_with(import: &str, module: &str) {
+    let declarations: Vec<_> = module
+        .lines()
+        .filter(|line| !line.starts_with("import "))
+        .map(|line| line.strip_prefix("export ").unwrap_or(line))
+        .collect();
+
+    check_with(import, declarations.join("\n"))
+}
+
+/// Evaluate a schema in node, after the `import`
+/// of its library
+///
+/// With the `validate` feature, zod schemas are
+/// evaluated in the embedded js engine instead.
 #[cfg(test)]
-fn check(schema: String) {
-    let schema = format!(r#"{} {schema}"#, crate::ZOD_IMPORT);
+pub(crate) fn check_with(import: &str, schema: String) {
+    #[cfg(feature = "validate")]
+    if import == crate::ZOD_IMPORT {
+        if let Err(err) =
+            crate::validate::validate_output(&schema, crate::validate::ZOD_MINI)
+        {
+            panic!("Failed to create schema in the js engine:\n{err}");
+        }
+        return;
+    }
+
+    let schema = format!(r#"{import} {schema}"#);
 
     let output = std::process::Command::new("node")
         .args(["-e", &schema])
@@ -24,3 +98,39 @@ fn check(schema: String) {
         String::from_utf8_lossy(&output.stderr)
     )
 }
+
+/// Type-check a TypeScript module with `tsc`, in
+/// the crate, so it finds the packages it imports
+#[cfg(test)]
+pub(crate) fn check_types(name: &str, module: &str) {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("target/check-types");
+    std::fs::create_dir_all(&dir).expect("failed to create the directory");
+    let path = dir.join(format!("{name}.ts"));
+    std::fs::write(&path, module).expect("failed to write the module");
+
+    let output = std::process::Command::new("npx")
+        .args([
+            "--no-install",
+            "tsc",
+            "--noEmit",
+            "--strict",
+            "--skipLibCheck",
+            "--target",
+            "es2022",
+            "--module",
+            "esnext",
+            "--moduleResolution",
+            "bundler",
+            "--lib",
+            "es2022,dom",
+        ])
+        .arg(&path)
+        .output()
+        .expect("failed to execute process");
+
+    assert!(
+        output.status.success(),
+        "Failed to type-check the module in tsc:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    )
+}
@@ -1,52 +1,14 @@
 use schemars::schema::SchemaObject;
 
-use crate::{ParserInner, ParserResult};
+use crate::{JsResult, ParserInner};
 
 impl ParserInner {
     /// Parse a number / integer
-    pub fn parse_number(&self, is_int: bool, object: &SchemaObject) -> ParserResult {
+    pub fn parse_number(&self, is_int: bool, object: &SchemaObject) -> JsResult {
         let options_default = Default::default();
         let options = object.number.as_ref().unwrap_or(&options_default);
 
-        let mut res = if is_int {
-            if options.minimum.is_some_and(|val| val == 0.) {
-                String::from("z.uint32()")
-            } else {
-                String::from("z.int32()")
-            }
-        } else {
-            String::from("z.float64()")
-        };
-
-        let mut checks = Vec::new();
-
-        if let Some(multiple_of) = options.multiple_of {
-            checks.push(format!("z.step({multiple_of})"));
-        }
-
-        if let Some(val) = options.minimum
-            && (val != 0. || !is_int)
-        {
-            checks.push(format!("z.minimum({val})"));
-        }
-
-        if let Some(val) = options.exclusive_minimum {
-            checks.push(format!("z.gt({val})"));
-        }
-
-        if let Some(val) = options.maximum {
-            checks.push(format!("z.maximum({val})"));
-        }
-
-        if let Some(val) = options.exclusive_maximum {
-            checks.push(format!("z.lt({val})"));
-        }
-
-        if !checks.is_empty() {
-            res.push_str(&format!(".check({})", checks.join(", ")));
-        }
-
-        Ok(res)
+        self.backend.number(is_int, options)
     }
 }
 
@@ -76,5 +38,6 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/number.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "number");
     }
 }
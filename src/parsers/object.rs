@@ -1,16 +1,21 @@
-use std::collections::BTreeMap;
-
 use schemars::schema::{Schema, SchemaObject};
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{
+    Error,
+    JsResult,
+    ParserInner,
+    PropertyOrder,
+    Scope,
+    backends::{AdditionalProperties, Property},
+};
 
 impl ParserInner {
     /// Parse a object
-    pub fn parse_object(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_object(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
         let options_default = Default::default();
         let options = object.object.as_ref().unwrap_or(&options_default);
 
-        let mut properties_parsed = BTreeMap::new();
+        let mut properties_parsed = Vec::with_capacity(options.properties.len());
 
         if options.min_properties.is_some()
             || options.max_properties.is_some()
@@ -24,88 +29,53 @@ impl ParserInner {
         }
 
         for (key, schema) in &options.properties {
-            let schema_parsed = self.parse_schema(schema)?;
-
-            let default = if let Schema::Object(schema) = schema {
-                if let Some(metadata) = &schema.metadata {
-                    metadata.default.as_ref()
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            let schema_parsed = if let Some(default) = default {
-                format!(
-                    "z._default({}, {})",
-                    schema_parsed,
-                    serde_json::to_string(default)?
-                )
-            } else if !options.required.contains(key) && !self.config.ignore_undefined {
-                if schema_parsed.starts_with("z.nullable(")
-                    && schema_parsed.ends_with(")")
-                {
-                    let schema_parsed = schema_parsed
-                        .strip_prefix("z.nullable(")
-                        .unwrap()
-                        .strip_suffix(")")
-                        .unwrap();
-                    format!("z.nullish({})", schema_parsed)
-                } else {
-                    format!("z.optional({})", schema_parsed)
-                }
-            } else {
-                schema_parsed
+            let (default, description) = match schema {
+                Schema::Object(schema) => (
+                    self.valid_default(schema).cloned(),
+                    schema
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.description.clone()),
+                ),
+                Schema::Bool(_) => (None, None),
             };
 
-            properties_parsed.insert(key.to_owned(), schema_parsed);
+            properties_parsed.push(Property {
+                key: key.to_owned(),
+                schema: self.parse_schema(schema, scope)?,
+                optional: !options.required.contains(key)
+                    && !self.config.ignore_undefined,
+                default,
+                description,
+            });
         }
 
-        let mut object_inner = Vec::with_capacity(properties_parsed.len());
-
-        for (k, v) in properties_parsed {
-            let k = serde_json::to_string(&k)?;
-
-            object_inner.push(format!("{k}: {v}"));
+        match self.config.property_order {
+            PropertyOrder::Alphabetical =>
+                properties_parsed.sort_by(|a, b| a.key.cmp(&b.key)),
+            PropertyOrder::Source => {},
+            PropertyOrder::RequiredFirst => properties_parsed
+                .sort_by_key(|property| !options.required.contains(&property.key)),
         }
 
-        let object_inner_parsed = if !object_inner.is_empty() {
-            Some(object_inner.join(", "))
-        } else {
-            None
+        let additional = match options.additional_properties.as_deref() {
+            None => AdditionalProperties::Unset,
+            Some(Schema::Bool(false)) => AdditionalProperties::Forbidden,
+            Some(additional @ Schema::Bool(true)) =>
+                AdditionalProperties::Any(self.parse_schema(additional, scope)?),
+            Some(additional) =>
+                AdditionalProperties::Schema(self.parse_schema(additional, scope)?),
         };
 
-        let object_parsed = if options
-            .additional_properties
-            .as_ref()
-            .is_some_and(|p| p.as_ref() == &Schema::Bool(true))
-        {
-            object_inner_parsed.map(|p| format!("z.strictObject({{ {} }})", p))
-        } else {
-            object_inner_parsed.map(|p| format!("z.object({{ {} }})", p))
-        };
+        let object_parsed = self.backend.object(properties_parsed, additional)?;
 
-        let object_parsed = if let Some(additional) = &options.additional_properties {
-            if additional.as_ref() != &Schema::Bool(false) {
-                let additional_parsed = self.parse_schema(additional)?;
-
-                if let Some(object_parsed) = object_parsed {
-                    format!("z.catchall({}, {})", object_parsed, additional_parsed)
-                } else {
-                    format!("z.record(z.string(), {})", additional_parsed)
-                }
-            } else if let Some(object_parsed) = object_parsed {
-                object_parsed
-            } else {
-                return Err(Error::Unimplemented(
-                    "Object: additional_properties are false, and there are no \
-                     properties given",
-                ));
-            }
-        } else {
-            object_parsed.unwrap_or_else(|| String::from("z.looseObject({})"))
-        };
+        if let Some(dependencies) = object.extensions.get("dependentRequired") {
+            let dependencies = serde_json::from_value(dependencies.clone())?;
+
+            return self
+                .backend
+                .dependent_required(object_parsed, &dependencies);
+        }
 
         Ok(object_parsed)
     }
@@ -119,7 +89,7 @@ mod tests {
     use schemars::{JsonSchema, schema::Schema};
     use uuid::Uuid;
 
-    use crate::{Parser, test_helpers::generator};
+    use crate::{Config, Parser, PropertyOrder, test_helpers::generator};
 
     #[derive(JsonSchema)]
     #[allow(dead_code)]
@@ -143,6 +113,7 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/object.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "object");
     }
 
     type TestType = HashMap<String, TestSchema>;
@@ -160,6 +131,7 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/record.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "record");
     }
 
     #[derive(JsonSchema)]
@@ -205,5 +177,49 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/flatten.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "flatten");
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TestSchema6 {
+        nickname: Option<String>,
+        age: u8,
+        name: String,
+    }
+
+    #[test]
+    fn test_object_required_first() {
+        let schema = generator().into_root_schema_for::<TestSchema6>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::new(Config {
+            property_order: PropertyOrder::RequiredFirst,
+            ..Default::default()
+        });
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"z.object({ "age": z.uint32(), "name": z.string(), "#,
+                r#""nickname": z.nullish(z.string()) })"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+
+        #[cfg(feature = "preserve_order")]
+        assert_eq!(
+            concat!(
+                r#"z.object({ "nickname": z.nullish(z.string()), "#,
+                r#""age": z.uint32(), "name": z.string() })"#,
+            ),
+            &Parser::new(Config {
+                property_order: PropertyOrder::Source,
+                ..Default::default()
+            })
+            .parse(&schema)
+            .unwrap()
+        );
     }
 }
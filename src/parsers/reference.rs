@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+
+use schemars::schema::SchemaObject;
+
+use crate::{Error, JsResult, ParserInner, Scope};
+
+/// The prefixes of references to a named schema
+const REFERENCE_PREFIXES: [&str; 3] =
+    ["#/components/schemas/", "#/definitions/", "#/$defs/"];
+
+impl ParserInner {
+    /// Check if the object is a reference and
+    /// `parse_reference` is safe to call
+    pub fn is_reference(&self, object: &SchemaObject) -> bool {
+        object.reference.is_some()
+    }
+
+    /// Get the name of the schema a reference
+    /// points to
+    pub fn reference_name<'a>(&self, reference: &'a str) -> Option<Cow<'a, str>> {
+        REFERENCE_PREFIXES
+            .iter()
+            .find_map(|prefix| reference.strip_prefix(prefix))
+            .filter(|name| !name.contains('/'))
+            .map(unescape_pointer)
+    }
+
+    /// Parse a reference to a named schema, which
+    /// is declared in the same module
+    pub fn parse_reference(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
+        let Some(reference) = &object.reference else {
+            return Err(Error::ForgotCheck(
+                "Reference: has to have the reference property",
+            ));
+        };
+
+        let Some(name) = self.reference_name(reference) else {
+            return Err(Error::Unimplemented(
+                "Reference: only references to components, definitions and $defs are \
+                 supported",
+            ));
+        };
+
+        if scope.is_pending(&name) {
+            self.backend.lazy(&self.declaration_name(&name, scope))
+        } else {
+            self.backend.reference(&self.declaration_name(&name, scope))
+        }
+    }
+}
+
+/// Decode a json pointer of a reference, which is
+/// percent-encoded in the fragment of an uri
+pub(crate) fn percent_decode(pointer: &str) -> Cow<'_, str> {
+    if !pointer.contains('%') {
+        return Cow::Borrowed(pointer);
+    }
+
+    let bytes = pointer.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            },
+        }
+    }
+
+    match String::from_utf8(decoded) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(_) => Cow::Borrowed(pointer),
+    }
+}
+
+/// Decode a segment of a json pointer, where `~1`
+/// is a `/` and `~0` is a `~`
+pub(crate) fn unescape_pointer(segment: &str) -> Cow<'_, str> {
+    let segment = percent_decode(segment);
+
+    if !segment.contains('~') {
+        return segment;
+    }
+
+    Cow::Owned(segment.replace("~1", "/").replace("~0", "~"))
+}
@@ -1,19 +1,24 @@
 use schemars::schema::Schema;
 
-use crate::{ParserInner, ParserResult};
+use crate::{JsResult, ParserInner, Scope};
 
 impl ParserInner {
     /// Parse a `Schema`
-    pub fn parse_schema(&self, schema: &Schema) -> ParserResult {
+    pub fn parse_schema(&self, schema: &Schema, scope: Scope<'_>) -> JsResult {
         Ok(match schema {
             Schema::Bool(bool) if *bool =>
                 if self.config.prefer_unknown {
-                    String::from("z.unknown()")
+                    self.backend.unknown()?
                 } else {
-                    String::from("z.any()")
+                    self.backend.any()?
                 },
-            Schema::Bool(bool) if !*bool => String::from("z.never()"),
-            Schema::Object(object) => self.parse_schema_object(object)?,
+            Schema::Bool(bool) if !*bool => self.backend.never()?,
+            Schema::Object(object) if self.has_default(object) => self.parse_default(
+                object,
+                self.parse_schema_object(object, scope)?,
+                scope,
+            )?,
+            Schema::Object(object) => self.parse_schema_object(object, scope)?,
             _ => unreachable!(),
         })
     }
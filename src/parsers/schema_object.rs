@@ -1,14 +1,28 @@
 use schemars::schema::{InstanceType, SchemaObject, SingleOrVec};
+use serde_json::Value;
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{Error, JsResult, ParserInner, Scope};
 
 impl ParserInner {
     /// Parse a `SchemaObject`
-    pub fn parse_schema_object(&self, object: &SchemaObject) -> ParserResult {
-        if self.is_union(&object) {
-            return self.parse_union(&object);
+    pub fn parse_schema_object(
+        &self,
+        object: &SchemaObject,
+        scope: Scope<'_>,
+    ) -> JsResult {
+        if self.is_nullable_extension(object) {
+            let mut object = object.clone();
+            object.extensions.remove("nullable");
+
+            return self
+                .backend
+                .nullable(self.parse_schema_object(&object, scope)?);
+        } else if self.is_reference(object) {
+            return self.parse_reference(object, scope);
+        } else if self.is_union(object) {
+            return self.parse_union(object, scope);
         } else if self.is_literal(object) {
-            return self.parse_literal(&object);
+            return self.parse_literal(object);
         }
 
         let Some(r#type) = &object.instance_type else {
@@ -31,13 +45,13 @@ impl ParserInner {
 
         Ok(match r#type {
             SingleOrVec::Single(instance_type) =>
-                self.match_instance_type(**instance_type, object)?,
+                self.match_instance_type(**instance_type, object, scope)?,
 
             // not supported anymore in zod/mini
             // if let Some(description) = description {
             //     format!(
             //         "{}.describe({})",
-            //         self.match_instance_type(**instance_type, object)?,
+            //         self.match_instance_type(**instance_type, object, scope)?,
             //         serde_json::to_string(&description)?
             //     )
             // }
@@ -49,36 +63,41 @@ impl ParserInner {
                 let is_nullable = instance_types.len() > null_filtered.len();
 
                 if let [instance_type] = instance_types.as_slice() {
-                    self.match_instance_type(*instance_type, object)?
+                    self.match_instance_type(*instance_type, object, scope)?
                 } else if is_nullable && null_filtered.len() == 1 {
                     let [instance_type] = null_filtered.as_slice() else {
                         unreachable!()
                     };
 
-                    format!(
-                        "z.nullable({})",
-                        self.match_instance_type(**instance_type, object)?
-                    )
+                    self.backend.nullable(self.match_instance_type(
+                        **instance_type,
+                        object,
+                        scope,
+                    )?)?
 
                     // not supported anymore in zod/mini
                     // if let Some(description) = description {
                     //     format!(
                     //         "z.nullable({}).describe({})",
                     //         self.match_instance_type(**instance_type,
-                    // object)?,
+                    // object, scope)?,
                     //         serde_json::to_string(&description)?
                     //     )
                     // }
                 } else {
                     let mut parsed = Vec::with_capacity(null_filtered.len());
                     for instance_type in null_filtered {
-                        parsed.push(self.match_instance_type(*instance_type, object)?);
+                        parsed.push(self.match_instance_type(
+                            *instance_type,
+                            object,
+                            scope,
+                        )?);
                     }
 
                     if is_nullable {
-                        format!("z.nullable(z.union([{}]))", parsed.join(", "),)
+                        self.backend.nullable(self.backend.union(parsed)?)?
                     } else {
-                        format!("z.union([{}])", parsed.join(", "),)
+                        self.backend.union(parsed)?
                     }
 
                     // not supported anymore in zod/mini
@@ -94,4 +113,84 @@ impl ParserInner {
             },
         })
     }
+
+    /// Check if the object is `nullable: true`, like
+    /// schemars outputs `Option<T>` for OpenAPI 3.0
+    /// and OpenAPI 3.0 documents are normalized, and
+    /// doesn't accept `null` already
+    fn is_nullable_extension(&self, object: &SchemaObject) -> bool {
+        let has_null = match &object.instance_type {
+            Some(SingleOrVec::Single(instance_type)) =>
+                **instance_type == InstanceType::Null,
+            Some(SingleOrVec::Vec(instance_types)) =>
+                instance_types.contains(&InstanceType::Null),
+            None => false,
+        };
+
+        object.extensions.get("nullable") == Some(&Value::Bool(true)) && !has_null
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::{JsonSchema, r#gen::SchemaSettings, schema::Schema};
+
+    use crate::Parser;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TestInner {
+        id: u8,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TestSchema {
+        name: Option<String>,
+        inner: Option<TestInner>,
+        tags: Vec<Option<bool>>,
+    }
+
+    #[test]
+    fn test_nullable_extension() {
+        let mut settings = SchemaSettings::openapi3();
+        settings.inline_subschemas = true;
+        let schema = settings
+            .into_generator()
+            .into_root_schema_for::<TestSchema>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::default();
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"z.object({ "inner": z.nullish(z.object({ "id": z.uint32() })), "#,
+                r#""name": z.nullish(z.string()), "#,
+                r#""tags": z.array(z.nullable(z.boolean())) })"#,
+            ),
+            &result
+        );
+        crate::parsers::check(result);
+    }
+
+    #[test]
+    fn test_nullable_extension_reference() {
+        let schema = SchemaSettings::openapi3()
+            .into_generator()
+            .into_root_schema_for::<TestSchema>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::default();
+        let result = parser.parse(&schema).unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"z.object({ "inner": z.nullish(TestInner), "#,
+                r#""name": z.nullish(z.string()), "#,
+                r#""tags": z.array(z.nullable(z.boolean())) })"#,
+            ),
+            &result
+        );
+    }
 }
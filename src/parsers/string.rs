@@ -1,89 +1,84 @@
 use schemars::schema::SchemaObject;
 
-use crate::{DateFormat, ParserInner, ParserResult};
+use crate::{DateFormat, Diagnostic, JsResult, ParserInner, Scope};
 
 impl ParserInner {
+    /// Get the `DateFormat` of a string format, or
+    /// `None` if it isn't a date
+    ///
+    /// `"time"` is not a date, it always stays a
+    /// string
+    pub fn date_format_of(&self, format: &str) -> Option<DateFormat> {
+        let formats = &self.config.date_formats;
+
+        let date_format = match format {
+            "date" => formats.date,
+            "date-time" => formats.date_time,
+            "partial-date-time" => formats.partial_date_time,
+            _ => return None,
+        };
+
+        Some(date_format.unwrap_or(self.config.date_format))
+    }
+
     /// Parse a string, or an enum
-    pub fn parse_string(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_string(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
         if object.enum_values.is_some() {
             return self.parse_enum(object);
+        } else if self.is_content(object) {
+            return self.parse_content(object, scope);
         }
 
-        let mut res = if let Some(format) = object.format.as_ref().map(|s| s.as_str()) {
-            let zod_function = match format {
-                "date-time" | "partial-date-time" | "date"
-                    if matches!(self.config.date_format, DateFormat::CoerceDate) =>
-                    return Ok(String::from("z.coerce.date()")),
-                "date-time" | "partial-date-time" | "date"
-                    if matches!(self.config.date_format, DateFormat::JsDate) =>
-                    return Ok(String::from("z.date()")),
-                "date-time" | "partial-date-time" | "date"
-                    if matches!(self.config.date_format, DateFormat::DateToJson) =>
-                    return Ok(String::from(
-                        "z.date().check(z.refine(date => date.toJSON()))",
-                    )),
-
-                "email" => "z.email()",
-                "uri" => "z.url()",
-                "uuid" => "z.guid()",
-                "ipv4" => "z.ipv4()",
-                "ipv6" => "z.ipv6()",
-                "hostname" => "z.hostname()",
-                "date-time" | "partial-date-time" =>
-                    "z.iso.datetime({ offset: true, local: true })",
-                "date" => "z.iso.date()",
-                "time" => "z.iso.time()",
-                "duration" => "z.iso.duration()",
-                _ => "z.string()",
-            };
-
-            String::from(zod_function)
-        } else {
-            String::from("z.string()")
-        };
-
-        let options_default = Default::default();
-        let options = object.string.as_ref().unwrap_or(&options_default);
-
-        let mut checks = Vec::new();
-
-        if let Some(min_length) = options.min_length
-            && let Some(max_length) = options.max_length
-            && min_length == max_length
-        {
-            checks.push(format!("z.length({min_length})"));
-        } else {
-            if let Some(min_items) = options.min_length {
-                checks.push(format!("z.minLength({min_items})"));
+        // a content encoding is validated just like a
+        // format
+        let format = object.format.as_deref().or_else(|| {
+            object
+                .extensions
+                .get("contentEncoding")
+                .and_then(|encoding| encoding.as_str())
+        });
+
+        if let Some(format) = format {
+            if self.is_codec(format) {
+                return self.parse_codec(format);
             }
 
-            if let Some(max_items) = options.max_length {
-                checks.push(format!("z.maxLength({max_items})"));
+            if let Some(date_format) = self.date_format_of(format)
+                && matches!(
+                    date_format,
+                    DateFormat::CoerceDate | DateFormat::JsDate | DateFormat::DateToJson
+                )
+            {
+                return self.backend.date(date_format, format);
             }
-        }
 
-        if let Some(pattern) = &options.pattern {
-            checks.push(format!(
-                "z.regex(new RegExp({}))",
-                serde_json::to_string(&pattern)?
-            ));
+            if !self.backend.is_format_supported(format) {
+                self.report(Diagnostic::UnknownFormat(format.to_owned()), scope);
+            }
         }
 
-        if !checks.is_empty() {
-            res.push_str(&format!(".check({})", checks.join(", ")));
-        }
+        let options_default = Default::default();
+        let options = object.string.as_ref().unwrap_or(&options_default);
 
-        Ok(res)
+        self.backend.string(format, options)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::{NaiveDate, NaiveDateTime};
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
     use schemars::{JsonSchema, schema::Schema};
+    use serde_json::json;
     use uuid::Uuid;
 
-    use crate::{Parser, test_helpers::generator};
+    use crate::{
+        Config,
+        DateFormat,
+        DateFormats,
+        Diagnostic,
+        Parser,
+        test_helpers::generator,
+    };
 
     #[derive(JsonSchema)]
     #[allow(dead_code)]
@@ -108,5 +103,70 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/string.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "string");
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "camelCase")]
+    struct TestSchema2 {
+        birthday: NaiveDate,
+        created_at: DateTime<Utc>,
+        updated_at: NaiveDateTime,
+    }
+
+    #[test]
+    fn test_string_date_formats() {
+        let schema = generator().into_root_schema_for::<TestSchema2>();
+        let schema = Schema::Object(schema.schema);
+
+        let parser = Parser::new(Config {
+            date_format: DateFormat::CoerceDate,
+            date_formats: DateFormats {
+                date: Some(DateFormat::IsoStringDate),
+                partial_date_time: Some(DateFormat::IsoStringDate),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result = parser.parse_pretty_default(&schema).unwrap();
+
+        // std::fs::write("tests/date-formats.js",
+        // result).expect("Could not save
+        // result");
+        assert_eq!(include_str!("../../tests/date-formats.js"), &result);
+        crate::parsers::check(result);
+        check_targets!(parser, &schema, "date-formats");
+    }
+
+    #[test]
+    fn test_string_formats() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "required": ["avatar", "color", "id", "network", "phone", "token"],
+            "properties": {
+                "avatar": { "type": "string", "contentEncoding": "base64" },
+                "color": { "type": "string", "format": "color" },
+                "id": { "type": "string", "format": "ulid" },
+                "network": { "type": "string", "format": "cidrv4" },
+                "phone": { "type": "string", "format": "e164" },
+                "token": { "type": "string", "format": "jwt" },
+            },
+        }))
+        .unwrap();
+
+        let parser = Parser::default();
+        let result = parser.parse_pretty_default(&schema).unwrap();
+
+        assert_eq!(parser.take_diagnostics(), vec![Diagnostic::UnknownFormat(
+            String::from("color")
+        )]);
+
+        // std::fs::write("tests/string-formats.js",
+        // result).expect("Could not save
+        // result");
+        assert_eq!(include_str!("../../tests/string-formats.js"), &result);
+        crate::parsers::check(result);
+        check_targets!(parser, &schema, "string-formats");
     }
 }
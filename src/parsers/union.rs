@@ -2,13 +2,27 @@ use schemars::{
     Set,
     schema::{InstanceType, Schema, SchemaObject, SingleOrVec},
 };
+use serde_json::Value;
 
-use crate::{Error, ParserInner, ParserResult};
+use crate::{Error, JsResult, ParserInner, Scope};
 
 impl ParserInner {
     /// Check if the union has one shared literal
-    /// key
-    pub fn has_discriminated(&self, variants: &Vec<Schema>) -> Option<String> {
+    /// key, or an explicit OpenAPI discriminator
+    pub fn has_discriminated(
+        &self,
+        object: &SchemaObject,
+        variants: &Vec<Schema>,
+    ) -> Option<String> {
+        if let Some(key) = object
+            .extensions
+            .get("discriminator")
+            .and_then(|discriminator| discriminator.get("propertyName"))
+            .and_then(|key| key.as_str())
+        {
+            return Some(key.to_owned());
+        }
+
         let variants_object = variants
             .iter()
             .filter_map(|schema| {
@@ -63,11 +77,14 @@ impl ParserInner {
     pub fn is_union(&self, object: &SchemaObject) -> bool { object.subschemas.is_some() }
 
     /// Parse a union
-    pub fn parse_union(&self, object: &SchemaObject) -> ParserResult {
+    pub fn parse_union(&self, object: &SchemaObject, scope: Scope<'_>) -> JsResult {
         let subschemas = object.subschemas.as_ref().unwrap();
 
+        // schemars wraps a reference with metadata, e.g.
+        // `nullable`, in an `allOf` of one schema
+        let single_all_of = subschemas.all_of.as_ref().filter(|v| v.len() == 1);
         let variants = subschemas.one_of.as_ref().map(|v| Ok(v)).unwrap_or_else(
-            || match &subschemas.any_of {
+            || match subschemas.any_of.as_ref().or(single_all_of) {
                 None => {
                     #[cfg(test)]
                     dbg!(object);
@@ -80,36 +97,95 @@ impl ParserInner {
         )?;
 
         if variants.is_empty() {
-            return Ok(if self.config.prefer_unknown {
-                String::from("z.unknown()")
+            return if self.config.prefer_unknown {
+                self.backend.unknown()
             } else {
-                String::from("z.any()")
-            });
+                self.backend.any()
+            };
         }
         if let [only] = variants.as_slice() {
-            return self.parse_schema(&only);
+            return self.parse_schema(&only, scope);
         }
 
-        let discriminated_key = self.has_discriminated(variants);
+        let discriminated_key = self.has_discriminated(object, variants);
+        let discriminator = object.extensions.get("discriminator");
+        let mapping = discriminator
+            .and_then(|discriminator| discriminator.get("mapping"))
+            .and_then(|mapping| mapping.as_object());
 
         let mut union_values = Vec::with_capacity(variants.len());
         for schema in variants {
-            union_values.push(self.parse_schema(schema)?);
+            let parsed = self.parse_schema(schema, scope)?;
+
+            // the discriminator values of the OpenAPI
+            // mapping, which point to this variant
+            let mut values: Vec<_> = match (&discriminated_key, mapping, schema) {
+                (Some(key), Some(mapping), Schema::Object(variant)) => mapping
+                    .iter()
+                    .filter(|(_, target)| {
+                        variant.reference.is_some()
+                            && target.as_str() == variant.reference.as_deref()
+                    })
+                    .map(|(value, _)| (key, Value::String(value.clone())))
+                    .collect(),
+                _ => Vec::new(),
+            };
+
+            // without a mapping, the name of the schema a
+            // variant references is its value
+            if values.is_empty()
+                && discriminator.is_some()
+                && let Some(key) = &discriminated_key
+                && let Schema::Object(variant) = schema
+                && let Some(name) = variant
+                    .reference
+                    .as_deref()
+                    .and_then(|reference| self.reference_name(reference))
+            {
+                values.push((key, Value::String(name.into_owned())));
+            }
+
+            if values.is_empty() {
+                union_values.push(parsed);
+                continue;
+            }
+
+            // a variant declared after the union can't be
+            // extended before it's declared
+            let pending = match schema {
+                Schema::Object(SchemaObject {
+                    reference: Some(reference),
+                    ..
+                }) => self
+                    .reference_name(reference)
+                    .filter(|name| scope.is_pending(name)),
+                _ => None,
+            };
+
+            for (key, value) in values {
+                union_values.push(match &pending {
+                    Some(name) => self.backend.lazy_with_discriminant(
+                        self.backend
+                            .reference(&self.declaration_name(name, scope))?,
+                        key,
+                        &value,
+                    )?,
+                    None =>
+                        self.backend
+                            .with_discriminant(parsed.clone(), key, &value)?,
+                });
+            }
         }
 
         let mut union_parsed = match discriminated_key {
-            Some(key) => format!(
-                "z.discriminatedUnion({}, [{}])",
-                serde_json::to_string(&key)?,
-                union_values.join(", "),
-            ),
-            None => format!("z.union([{}])", union_values.join(", ")),
+            Some(key) => self.backend.discriminated_union(&key, union_values)?,
+            None => self.backend.union(union_values)?,
         };
 
         if object.object.is_some() {
-            let and = self.parse_object(object)?;
+            let and = self.parse_object(object, scope)?;
 
-            union_parsed = format!("z.intersection({union_parsed}, {and})")
+            union_parsed = self.backend.intersection(union_parsed, and)?
         }
 
         Ok(union_parsed)
@@ -145,6 +221,7 @@ mod tests {
         // result");
         assert_eq!(include_str!("../../tests/tagged-union.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "tagged-union");
     }
 
     #[derive(JsonSchema)]
@@ -168,6 +245,7 @@ mod tests {
 
         assert_eq!(include_str!("../../tests/double-tagged-union.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "double-tagged-union");
         // std::fs::write("tests/
         // double-tagged-union.js",
         // result).expect("Could not save
@@ -193,6 +271,7 @@ mod tests {
 
         assert_eq!(include_str!("../../tests/untagged-union.js"), &result);
         crate::parsers::check(result);
+        check_targets!(parser, &schema, "untagged-union");
         // std::fs::write("tests/untagged-union.
         // js", result).expect("Could not save
         // result");
@@ -0,0 +1,139 @@
+//! Snapshot tests of generated schemas, so changes
+//! of the output show up in review
+
+use std::path::Path;
+
+use schemars::{
+    JsonSchema,
+    r#gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema,
+};
+
+use crate::Parser;
+
+/// Set to `1` to write the snapshots, instead of
+/// comparing against them
+pub const BLESS_VAR: &str = "ZOD_SNAPSHOT_BLESS";
+
+/// Compare the schema of `T` to the snapshot at
+/// `path`, or write it when `ZOD_SNAPSHOT_BLESS=1`
+///
+/// Uses the default pretty config with the
+/// `pretty` feature, so the snapshots change with
+/// the feature.
+pub fn assert_snapshot<T: JsonSchema>(parser: &Parser, path: &Path) {
+    let mut settings = SchemaSettings::draft07();
+    settings.inline_subschemas = true;
+    let schema = SchemaGenerator::new(settings).into_root_schema_for::<T>();
+    let schema = Schema::Object(schema.schema);
+
+    #[cfg(feature = "pretty")]
+    let result = parser.parse_pretty_default(&schema);
+    #[cfg(not(feature = "pretty"))]
+    let result = parser.parse(&schema);
+    let result = result.unwrap_or_else(|err| panic!("Could not parse the schema: {err}"));
+
+    if std::env::var(BLESS_VAR).is_ok_and(|bless| bless == "1") {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .expect("Could not create the snapshot directory");
+        }
+        std::fs::write(path, &result).expect("Could not save the snapshot");
+        return;
+    }
+
+    let Ok(snapshot) = std::fs::read_to_string(path) else {
+        panic!(
+            "There is no snapshot at {}, run with {BLESS_VAR}=1 to create it",
+            path.display()
+        );
+    };
+
+    assert_eq!(
+        snapshot,
+        result,
+        "The schema changed, run with {BLESS_VAR}=1 to update {}",
+        path.display()
+    );
+}
+
+/// Compare the zod schema of a type to its snapshot
+/// in `tests/snapshots/<name>.js`
+///
+/// The name defaults to the schema name of the
+/// type, and the parser to the default one. Run
+/// with `ZOD_SNAPSHOT_BLESS=1` to write new or
+/// changed snapshots.
+///
+/// ```ignore
+/// #[test]
+/// fn user_snapshot() {
+///     assert_zod_snapshot!(User);
+///     assert_zod_snapshot!(Vec<User>, "users");
+///     assert_zod_snapshot!(parser = &parser, User, "user-valibot");
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_zod_snapshot {
+    (parser = $parser:expr, $type:ty) => {
+        $crate::assert_zod_snapshot!(
+            parser = $parser,
+            $type,
+            <$type as $crate::schemars::JsonSchema>::schema_name()
+        )
+    };
+    (parser = $parser:expr, $type:ty, $name:expr) => {
+        $crate::snapshot::assert_snapshot::<$type>(
+            $parser,
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.js", $name)),
+        )
+    };
+    ($type:ty) => {
+        $crate::assert_zod_snapshot!(
+            $type,
+            <$type as $crate::schemars::JsonSchema>::schema_name()
+        )
+    };
+    ($type:ty, $name:expr) => {
+        $crate::snapshot::assert_snapshot::<$type>(
+            &$crate::Parser::default(),
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.js", $name)),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::JsonSchema;
+
+    use crate::{Config, Parser, Target};
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TestSchema {
+        admin: bool,
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_snapshot() {
+        crate::assert_zod_snapshot!(TestSchema);
+        crate::assert_zod_snapshot!(Vec<TestSchema>, "array-of-test-schema");
+    }
+
+    #[test]
+    fn test_snapshot_parser() {
+        let parser = Parser::new(Config {
+            target: Target::Valibot,
+            ..Default::default()
+        });
+
+        crate::assert_zod_snapshot!(parser = &parser, TestSchema, "valibot-test-schema");
+    }
+}
@@ -0,0 +1,102 @@
+use boa_engine::{Context, Source};
+
+use crate::Error;
+
+/// The zod/mini bundle of `scripts/vendor-zod-mini.sh`
+#[cfg(test)]
+pub(crate) const ZOD_MINI: &str = include_str!("../vendor/zod-mini.js");
+
+/// Evaluate the output of a parser in an embedded
+/// js engine, without node
+///
+/// `library` is a script defining the global of
+/// the import, e.g. a bundle of `zod/mini` setting
+/// `globalThis.z`. The imports are left out of the
+/// output, and its declarations aren't exported.
+///
+/// Only js is evaluated, so TypeScript outputs
+/// can't be validated.
+///
+/// ```ignore
+/// let output = Parser::default().parse(&schema)?;
+///
+/// validate_output(&output, include_str!("zod-mini.js"))?;
+/// ```
+pub fn validate_output(output: &str, library: &str) -> Result<(), Error> {
+    let mut script = Vec::new();
+    let mut in_import = false;
+
+    for line in output.lines() {
+        // an import can span multiple lines, until
+        // its specifier
+        if in_import || line.starts_with("import ") {
+            in_import = !line.trim_end().ends_with(';');
+            continue;
+        }
+
+        script.push(line.strip_prefix("export ").unwrap_or(line));
+    }
+    let script = script.join("\n");
+
+    let mut context = Context::default();
+
+    for source in [library, &script] {
+        context
+            .eval(Source::from_bytes(source))
+            .map_err(|err| Error::ValidationError(err.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ZOD_MINI, validate_output};
+    use crate::{Error, Parser};
+
+    #[test]
+    fn test_validate_output() {
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "owner": { "$ref": "#/components/schemas/Person" } },
+                    },
+                    "Person": {
+                        "type": "object",
+                        "properties": {
+                            "pets": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Pet" },
+                            },
+                        },
+                    },
+                },
+            },
+        });
+
+        let output = Parser::default().parse_openapi(&document).unwrap();
+        validate_output(&output, ZOD_MINI).unwrap();
+
+        let output = concat!(
+            "import {\n",
+            "  Person,\n",
+            "} from \"./people\";\n",
+            "export const Pet = z.object({ \"owner\": z.string() });\n",
+            "Pet.parse({ owner: \"Ann\" });\n",
+        );
+        validate_output(output, ZOD_MINI).unwrap();
+
+        assert!(matches!(
+            validate_output("export const Pet = Person;", ZOD_MINI),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(matches!(
+            validate_output("z.object({ \"a\": z.string() }).parse({ a: 1 });", ZOD_MINI),
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
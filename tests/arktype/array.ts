@@ -0,0 +1,4 @@
+type({
+  admin: type('boolean'),
+  'age?': type('number.integer').or('null'),
+}).array();
@@ -0,0 +1,7 @@
+type({
+  birthday: type(/^\d{4}-\d{2}-\d{2}$/),
+  createdAt: type('string.date.parse'),
+  updatedAt: type(
+    /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?$/,
+  ),
+});
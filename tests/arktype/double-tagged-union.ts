@@ -0,0 +1,26 @@
+type({ kind: type.unit('justTheName') })
+  .or(
+    type({
+      kind: type.unit('nameAndSingleValue'),
+      value: type('number.integer'),
+    }),
+  )
+  .or(
+    type({
+      kind: type.unit('nameAndTuple'),
+      value: type([
+        type('string'),
+        type('string'),
+        type('string'),
+      ]),
+    }),
+  )
+  .or(
+    type({
+      kind: type.unit('nameAndObject'),
+      value: type({
+        int: type('number.integer'),
+        prop: type('string'),
+      }),
+    }),
+  );
@@ -0,0 +1 @@
+type.enumerated('message', 'leave', 'walk', 'buyATV');
@@ -0,0 +1,14 @@
+type({ key: type('string'), kind: type.unit('Option1') })
+  .or(
+    type({
+      key2: type('number.integer').atLeast(0),
+      kind: type.unit('Option2'),
+    }),
+  )
+  .and(
+    type({
+      a: type('number.integer'),
+      'date?': type('string.date.parse').or('null'),
+      'max?': type('number.integer').atLeast(0).or('null'),
+    }),
+  );
@@ -0,0 +1,4 @@
+type({
+  age: type('number.integer'),
+  distance: type('number'),
+});
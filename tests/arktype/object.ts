@@ -0,0 +1 @@
+type({ userId: type('string.uuid') });
@@ -0,0 +1 @@
+type({ '[string]': type({ userId: type('string.uuid') }) });
@@ -0,0 +1,8 @@
+type({
+  avatar: type('string.base64'),
+  color: type('string'),
+  id: type(/^[0-9A-HJKMNP-TV-Z]{26}$/i),
+  network: type('string'),
+  phone: type(/^\+[1-9]\d{6,14}$/),
+  token: type('string'),
+});
@@ -0,0 +1,6 @@
+type({
+  birthday: type('string.date.parse'),
+  createdAt: type('string.date.parse'),
+  name: type('string'),
+  userId: type('string.uuid'),
+});
@@ -0,0 +1,12 @@
+type({ kind: type.unit('justTheName') })
+  .or(
+    type({ kind: type.unit('nameAndSingleValue') })
+      .or(type('number.integer')),
+  )
+  .or(
+    type({
+      int: type('number.integer'),
+      kind: type.unit('nameAndObject'),
+      prop: type('string'),
+    }),
+  );
@@ -0,0 +1,7 @@
+type({ int: type('number.integer'), prop: type('string') })
+  .or(
+    type({
+      name: type('string'),
+      prop: type('number.integer'),
+    }),
+  );
@@ -0,0 +1,49 @@
+import * as z from "zod/mini";
+
+export const Pet = z.object({ "born": z.codec(z.iso.datetime({ offset: true }), z.date(), { decode: s => new Date(s), encode: d => d.toISOString() }), "name": z.string() });
+
+export const clientConfig: { baseUrl: string; init: RequestInit & { headers?: Record<string, string> } } = { baseUrl: "https://example.com/api", init: {} };
+
+function searchParams(query: Record<string, unknown>): string {
+  const search = new URLSearchParams();
+  for (const [key, value] of Object.entries(query)) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item !== undefined && item !== null) search.append(key, String(item));
+    }
+  }
+  const result = search.toString();
+  return result ? `?${result}` : "";
+}
+
+export const ListPetsQuery = z.object({ "limit": z.optional(z.uint32()) });
+
+export const ListPetsResponse = z.array(Pet);
+
+export async function listPets(query: z.input<typeof ListPetsQuery> = {}, init?: RequestInit) {
+  const response = await fetch(`${clientConfig.baseUrl}/pets${searchParams(query)}`, { ...clientConfig.init, ...init, method: "GET", headers: { ...clientConfig.init.headers, ...init?.headers } });
+  if (!response.ok) throw new Error(`${response.status} ${response.statusText}`);
+  return ListPetsResponse.parse(await response.json());
+}
+
+export const GetPetPath = z.object({ "petId": z.string() });
+
+export const GetPetQuery = z.object({ "fields": z.array(z.string()) });
+
+export const GetPetResponse = Pet;
+
+export async function getPet(path: z.input<typeof GetPetPath>, query: z.input<typeof GetPetQuery>, init?: RequestInit) {
+  const response = await fetch(`${clientConfig.baseUrl}/pets/${encodeURIComponent(String(path["petId"]))}${searchParams(query)}`, { ...clientConfig.init, ...init, method: "GET", headers: { ...clientConfig.init.headers, ...init?.headers } });
+  if (!response.ok) throw new Error(`${response.status} ${response.statusText}`);
+  return GetPetResponse.parse(await response.json());
+}
+
+export const Put_pets_petIdPath = z.object({ "petId": z.string() });
+
+export const Put_pets_petIdBody = Pet;
+
+/** Replace a pet */
+export async function put_pets_petId(path: z.input<typeof Put_pets_petIdPath>, body: z.output<typeof Put_pets_petIdBody>, init?: RequestInit) {
+  const response = await fetch(`${clientConfig.baseUrl}/pets/${encodeURIComponent(String(path["petId"]))}`, { ...clientConfig.init, ...init, method: "PUT", headers: { ...clientConfig.init.headers, ...init?.headers, "Content-Type": "application/json" }, body: JSON.stringify(z.encode(Put_pets_petIdBody, body)) });
+  if (!response.ok) throw new Error(`${response.status} ${response.statusText}`);
+  return;
+}
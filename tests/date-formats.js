@@ -0,0 +1,5 @@
+z.object({
+  birthday: z.iso.date(),
+  createdAt: z.coerce.date(),
+  updatedAt: z.iso.datetime({ local: true }),
+});
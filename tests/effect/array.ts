@@ -0,0 +1,6 @@
+Schema.Array(
+  Schema.Struct({
+    admin: Schema.Boolean,
+    age: Schema.optional(Schema.NullOr(Schema.Int)),
+  }),
+);
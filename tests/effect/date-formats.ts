@@ -0,0 +1,11 @@
+Schema.Struct({
+  birthday: Schema.String.pipe(
+    Schema.pattern(/^\d{4}-\d{2}-\d{2}$/),
+  ),
+  createdAt: Schema.Date,
+  updatedAt: Schema.String.pipe(
+    Schema.pattern(
+      /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?$/,
+    ),
+  ),
+});
@@ -0,0 +1,22 @@
+Schema.Union(
+  Schema.Struct({ kind: Schema.Literal('justTheName') }),
+  Schema.Struct({
+    kind: Schema.Literal('nameAndSingleValue'),
+    value: Schema.Int,
+  }),
+  Schema.Struct({
+    kind: Schema.Literal('nameAndTuple'),
+    value: Schema.Tuple(
+      Schema.String,
+      Schema.String,
+      Schema.String,
+    ),
+  }),
+  Schema.Struct({
+    kind: Schema.Literal('nameAndObject'),
+    value: Schema.Struct({
+      int: Schema.Int,
+      prop: Schema.String,
+    }),
+  }),
+);
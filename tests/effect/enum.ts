@@ -0,0 +1 @@
+Schema.Literal('message', 'leave', 'walk', 'buyATV');
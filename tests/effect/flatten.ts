@@ -0,0 +1,21 @@
+Schema.extend(
+  Schema.Union(
+    Schema.Struct({
+      key: Schema.String,
+      kind: Schema.Literal('Option1'),
+    }),
+    Schema.Struct({
+      key2: Schema.Int.pipe(Schema.greaterThanOrEqualTo(0)),
+      kind: Schema.Literal('Option2'),
+    }),
+  ),
+  Schema.Struct({
+    a: Schema.Int,
+    date: Schema.optional(Schema.NullOr(Schema.Date)),
+    max: Schema.optional(
+      Schema.NullOr(
+        Schema.Int.pipe(Schema.greaterThanOrEqualTo(0)),
+      ),
+    ),
+  }),
+);
@@ -0,0 +1 @@
+Schema.Struct({ age: Schema.Int, distance: Schema.Number });
@@ -0,0 +1 @@
+Schema.Struct({ userId: Schema.UUID });
@@ -0,0 +1,4 @@
+Schema.Record({
+  key: Schema.String,
+  value: Schema.Struct({ userId: Schema.UUID }),
+});
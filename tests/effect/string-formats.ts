@@ -0,0 +1,14 @@
+Schema.Struct({
+  avatar: Schema.String.pipe(
+    Schema.pattern(
+      /^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$/,
+    ),
+  ),
+  color: Schema.String,
+  id: Schema.ULID,
+  network: Schema.String,
+  phone: Schema.String.pipe(
+    Schema.pattern(/^\+[1-9]\d{6,14}$/),
+  ),
+  token: Schema.String,
+});
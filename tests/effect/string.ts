@@ -0,0 +1,6 @@
+Schema.Struct({
+  birthday: Schema.Date,
+  createdAt: Schema.Date,
+  name: Schema.String,
+  userId: Schema.UUID,
+});
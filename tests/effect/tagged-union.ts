@@ -0,0 +1,14 @@
+Schema.Union(
+  Schema.Struct({ kind: Schema.Literal('justTheName') }),
+  Schema.Union(
+    Schema.Struct({
+      kind: Schema.Literal('nameAndSingleValue'),
+    }),
+    Schema.Int,
+  ),
+  Schema.Struct({
+    int: Schema.Int,
+    kind: Schema.Literal('nameAndObject'),
+    prop: Schema.String,
+  }),
+);
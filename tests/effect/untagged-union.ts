@@ -0,0 +1,4 @@
+Schema.Union(
+  Schema.Struct({ int: Schema.Int, prop: Schema.String }),
+  Schema.Struct({ name: Schema.String, prop: Schema.Int }),
+);
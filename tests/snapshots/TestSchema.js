@@ -0,0 +1,4 @@
+z.object({
+  admin: z.boolean(),
+  name: z.nullish(z.string()),
+});
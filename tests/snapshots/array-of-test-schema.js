@@ -0,0 +1,6 @@
+z.array(
+  z.object({
+    admin: z.boolean(),
+    name: z.nullish(z.string()),
+  }),
+);
@@ -0,0 +1,4 @@
+v.object({
+  admin: v.boolean(),
+  name: v.nullish(v.string()),
+});
@@ -0,0 +1,8 @@
+z.object({
+  avatar: z.base64(),
+  color: z.string(),
+  id: z.ulid(),
+  network: z.cidrv4(),
+  phone: z.e164(),
+  token: z.jwt(),
+});